@@ -0,0 +1,26 @@
+//! Provide the `memory.x` of the selected board to the linker, `layout.ld` includes it.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    let board = if env::var_os("CARGO_FEATURE_TEENSY_30").is_some() {
+        "teensy30"
+    } else if env::var_os("CARGO_FEATURE_TEENSY_35").is_some() {
+        "teensy35"
+    } else if env::var_os("CARGO_FEATURE_TEENSY_36").is_some() {
+        "teensy36"
+    } else {
+        // the teensy 3.1 and 3.2 share the same MCU, they are the default
+        "teensy32"
+    };
+
+    let memory = format!("memory/{}.x", board);
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::copy(&memory, out.join("memory.x")).unwrap();
+
+    println!("cargo:rustc-link-search={}", out.display());
+    println!("cargo:rerun-if-changed={}", memory);
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -9,7 +9,8 @@ define_panic! {empty}
 
 #[no_mangle]
 fn main() {
-    let mut led = unsafe { make_pin!(led).make_gpio().output() };
+    let pins = port::Pins::take().unwrap();
+    let mut led = pins.p13.make_gpio().output();
 
     loop {
         led.try_toggle().unwrap();
@@ -9,15 +9,9 @@ define_panic! {empty}
 
 #[no_mangle]
 fn main() {
-    let (led, mut pin) = unsafe { make_pin!(led, 6) };
-
-    unsafe {
-        pin.set_pin_pe(true);
-        pin.set_pin_ps(false);
-    }
-
-    let mut led_out = led.make_gpio().output();
-    let pin_in = pin.make_gpio().input();
+    let pins = port::Pins::take().unwrap();
+    let mut led_out = pins.p13.make_gpio().output();
+    let pin_in = pins.p6.make_gpio().into_pull_down_input();
 
     loop {
         match pin_in.try_is_high().unwrap() {
@@ -0,0 +1,38 @@
+#![feature(stdsimd)]
+#![no_std]
+#![no_main]
+
+use embedded_hal::prelude::*;
+use teensy::port::{Gpio, Output};
+use teensy::*;
+
+define_panic! {empty}
+
+static mut LED: Option<Gpio<Output>> = None;
+
+#[no_mangle]
+fn main() {
+    let pins = port::Pins::take().unwrap();
+    let mut pin = pins.p6.erase();
+
+    unsafe {
+        // ignore the bounces of the button, shorter than 10ms
+        port::Port::new(port::PortName::D)
+            .set_debounce(time::Duration::from_millis(10))
+            .unwrap();
+        pin.set_pin_dfe(true);
+        LED = Some(pins.p13.make_gpio().output());
+    }
+
+    // toggle the led each time the pin 6 is connected to the ground
+    let mut button = pin.make_gpio().into_pull_up_input();
+    button.enable_interrupt(port::Edge::Falling, || {
+        if let Some(led) = unsafe { LED.as_mut() } {
+            led.try_toggle().unwrap();
+        }
+    });
+
+    loop {
+        sleep::wait_for_interrupt();
+    }
+}
@@ -10,13 +10,9 @@ define_panic! {empty}
 
 #[no_mangle]
 fn main() {
-    let (led, sim, uart) = unsafe {
-        (
-            make_pin!(led),
-            sim::Sim::new(),
-            uart::UART::new(uart::UART0),
-        )
-    };
+    let pins = port::Pins::take().unwrap();
+    let (led, uart) = (pins.p13, uart::UART::uart0(pins.p0, pins.p1).unwrap());
+    let sim = unsafe { sim::Sim::new() };
     unsafe {
         uart.setup(sim, 115200);
     }
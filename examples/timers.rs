@@ -0,0 +1,37 @@
+#![feature(stdsimd)]
+#![no_std]
+#![no_main]
+
+use embedded_hal::prelude::*;
+use teensy::port::{Gpio, Output};
+use teensy::*;
+
+define_panic! {empty}
+
+static mut LED: Option<Gpio<Output>> = None;
+
+#[no_mangle]
+fn main() {
+    let pins = port::Pins::take().unwrap();
+    unsafe {
+        LED = Some(pins.p13.make_gpio().output());
+    }
+
+    // blink the led, 3 quick blinks every 2 seconds
+    timers::every(time::Duration::from_secs(2), || {
+        for n in 0..6 {
+            timers::after(time::Duration::from_millis(100 * n + 1), toggle_led).unwrap();
+        }
+    })
+    .unwrap();
+
+    loop {
+        sleep::wait_for_interrupt();
+    }
+}
+
+fn toggle_led() {
+    if let Some(led) = unsafe { LED.as_mut() } {
+        led.try_toggle().unwrap();
+    }
+}
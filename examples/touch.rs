@@ -0,0 +1,29 @@
+#![feature(stdsimd)]
+#![no_std]
+#![no_main]
+
+use embedded_hal::prelude::*;
+use teensy::*;
+
+define_panic! {empty}
+
+#[no_mangle]
+fn main() {
+    let pins = port::Pins::take().unwrap();
+    let mut led = pins.p13.make_gpio().output();
+    let mut pad = pins.p23.erase();
+
+    // the count without finger depends on the wire and the pad
+    let idle = tsi::touch_read(&mut pad).unwrap() as u32;
+
+    // light the led while the pin 23 is touched
+    loop {
+        let count = tsi::touch_read(&mut pad).unwrap() as u32;
+        if count > idle * 3 / 2 {
+            led.try_set_high().unwrap();
+        } else {
+            led.try_set_low().unwrap();
+        }
+        sleep::sleep_ms(20);
+    }
+}
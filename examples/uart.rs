@@ -12,13 +12,10 @@ define_panic! {empty}
 fn main() {
     let mut time = core::time::Duration::new(0, 0);
 
-    let (mut led, sim, uart) = unsafe {
-        (
-            make_pin!(led).make_gpio().output(),
-            sim::Sim::new(),
-            uart::UART::new(uart::UART0),
-        )
-    };
+    let pins = port::Pins::take().unwrap();
+    let mut led = pins.p13.make_gpio().output();
+    let uart = uart::UART::uart0(pins.p0, pins.p1).unwrap();
+    let sim = unsafe { sim::Sim::new() };
     unsafe {
         uart.setup(sim, 115200);
     }
@@ -0,0 +1,6 @@
+/* Teensy 3.0: MK20DX128VLH5 */
+MEMORY
+{
+	FLASH (rx) : ORIGIN = 0x00000000, LENGTH = 128K
+	RAM  (rwx) : ORIGIN = 0x1FFFE000, LENGTH = 16K
+}
@@ -0,0 +1,11 @@
+/*
+ * Teensy 3.1 and 3.2: MK20DX256VLH7 with 256K flash starting at 0.
+ * For the 64K of RAM, SRAM_U starting at 0x2000 0000 translates to a
+ * start address of 0x1fff8000
+ */
+MEMORY
+{
+	FLASH (rx) : ORIGIN = 0x00000000, LENGTH = 256K
+	/*think about splitting up the RAM into SRAM_U and SRAM_L*/
+	RAM  (rwx) : ORIGIN = 0x1FFF8000, LENGTH = 64K
+}
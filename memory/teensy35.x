@@ -0,0 +1,6 @@
+/* Teensy 3.5: MK64FX512VMD12 */
+MEMORY
+{
+	FLASH (rx) : ORIGIN = 0x00000000, LENGTH = 512K
+	RAM  (rwx) : ORIGIN = 0x1FFF0000, LENGTH = 192K
+}
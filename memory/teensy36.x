@@ -0,0 +1,6 @@
+/* Teensy 3.6: MK66FX1M0VMD18 */
+MEMORY
+{
+	FLASH (rx) : ORIGIN = 0x00000000, LENGTH = 1024K
+	RAM  (rwx) : ORIGIN = 0x1FFF0000, LENGTH = 256K
+}
@@ -0,0 +1,159 @@
+//! # Bit-band
+//! The cortex-m4 maps each bit of the first megabyte of the SRAM_U (0x2000_0000) and of the
+//! peripherals (0x4000_0000) to a 32 bits word of an alias region (0x2200_0000 and 0x4200_0000).
+//! Writing 1 or 0 to the alias word sets or clears the bit in a single bus access: no other
+//! code, not even an interrupt, can run between the read and the write like with a
+//! read/modify/write. Reading the alias word reads the bit.
+//!
+//! The `port::Gpio` already uses the alias of the GPIO registers, `BitBand` does the same for
+//! any register or SRAM word:
+//! ```rust
+//! // the flag can be raised from an interrupt handler while main clears it
+//! #[link_section = ".bitband"]
+//! static FLAGS: AtomicU32 = AtomicU32::new(0);
+//! let ready = bitband::BitBand::sram(&FLAGS, 3).unwrap();
+//! ready.set();
+//!
+//! // UART0 C2 is a 8 bits register, TE is its bit 3
+//! let te = unsafe { bitband::BitBand::<u8>::new(0x4006_A003 as *mut u8, 3) }.unwrap();
+//! te.clear();
+//! ```
+//!
+//! **Only the SRAM_U can be aliased**, the SRAM_L below 0x2000_0000 can't. The statics are
+//! placed in the SRAM_L by `layout.ld`, put them in the `.bitband` section with
+//! `#[link_section = ".bitband"]` to move them to the start of the SRAM_U. This section is
+//! zeroed at boot, its statics must be initialized to 0. `BitBand::sram` returns an error when
+//! the word is in the SRAM_L.
+
+use core::marker::PhantomData;
+use core::sync::atomic::AtomicU32;
+
+const SRAM_BASE: u32 = 0x2000_0000;
+const SRAM_ALIAS: u32 = 0x2200_0000;
+const PERIPHERAL_BASE: u32 = 0x4000_0000;
+const PERIPHERAL_ALIAS: u32 = 0x4200_0000;
+/// Both regions are 1MB
+const REGION_SIZE: u32 = 0x10_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The address is neither in the first megabyte of the SRAM_U nor of the peripherals
+    NotBitBandable(u32),
+    /// The bit is wider than the word
+    InvalidBit(u8),
+}
+
+/// The alias word of the bit `bit` of the byte at `address`. Bits above 7 are in the next bytes.
+pub fn alias(address: u32, bit: u8) -> Result<u32, Error> {
+    let (base, alias) = match address {
+        a if a >= SRAM_BASE && a < SRAM_BASE + REGION_SIZE => (SRAM_BASE, SRAM_ALIAS),
+        a if a >= PERIPHERAL_BASE && a < PERIPHERAL_BASE + REGION_SIZE => {
+            (PERIPHERAL_BASE, PERIPHERAL_ALIAS)
+        }
+        _ => return Err(Error::NotBitBandable(address)),
+    };
+
+    let byte = address + bit as u32 / 8;
+    if byte >= base + REGION_SIZE {
+        return Err(Error::NotBitBandable(byte));
+    }
+    Ok(alias + (byte - base) * 32 + (bit as u32 % 8) * 4)
+}
+
+/// The width of the word holding the bit. The alias is accessed with the same width, the
+/// peripherals on the 8 bits bus, like the UART, must be accessed with `u8`.
+pub trait Word: Copy + PartialEq + private::Sealed {
+    const BITS: u8;
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+impl Word for u8 {
+    const BITS: u8 = 8;
+    const ZERO: u8 = 0;
+    const ONE: u8 = 1;
+}
+
+impl Word for u16 {
+    const BITS: u8 = 16;
+    const ZERO: u16 = 0;
+    const ONE: u16 = 1;
+}
+
+impl Word for u32 {
+    const BITS: u8 = 32;
+    const ZERO: u32 = 0;
+    const ONE: u32 = 1;
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+/// A single bit of a `T` word, set, cleared and read atomically.
+///
+/// **On a peripheral register the write is a read/modify/write done by the bus**: the whole
+/// register is read and written back with only this bit changed. Any other write-1-to-clear
+/// flag set at this moment is written back as 1 and cleared, like the status flags of the UART,
+/// of the PORT (ISFR) or of the TSI. Only use it on registers without such flags.
+#[derive(Clone, Copy)]
+pub struct BitBand<T: Word = u32> {
+    alias: *mut T,
+    _word: PhantomData<T>,
+}
+
+// Every access is a single bus access to the alias
+unsafe impl<T: Word> Send for BitBand<T> {}
+unsafe impl<T: Word> Sync for BitBand<T> {}
+
+impl<T: Word> BitBand<T> {
+    /// The bit `bit` of the word at `word`, a register or a SRAM_U word.
+    /// Unsafe because nothing checks what is at this address.
+    pub unsafe fn new(word: *mut T, bit: u8) -> Result<BitBand<T>, Error> {
+        if bit >= T::BITS {
+            return Err(Error::InvalidBit(bit));
+        }
+
+        Ok(BitBand {
+            alias: alias(word as u32, bit)? as *mut T,
+            _word: PhantomData,
+        })
+    }
+
+    pub fn set(&self) {
+        self.write(true);
+    }
+
+    pub fn clear(&self) {
+        self.write(false);
+    }
+
+    pub fn write(&self, value: bool) {
+        let value = if value { T::ONE } else { T::ZERO };
+        unsafe { core::ptr::write_volatile(self.alias, value) }
+    }
+
+    pub fn read(&self) -> bool {
+        unsafe { core::ptr::read_volatile(self.alias) != T::ZERO }
+    }
+
+    /// The address of the alias word.
+    pub fn alias(&self) -> u32 {
+        self.alias as u32
+    }
+}
+
+impl BitBand<u32> {
+    /// The bit `bit` of a static word, which must be in the SRAM_U: see the `.bitband` section
+    /// in the module documentation.
+    ///
+    /// The alias is written with a plain volatile store, not through the `AtomicU32` API. Each
+    /// bit access is atomic, but the other accesses to the word must use the atomic operations
+    /// (`load`, `fetch_or`...), never a non-atomic read/modify/write through a raw pointer.
+    pub fn sram(word: &'static AtomicU32, bit: u8) -> Result<BitBand<u32>, Error> {
+        unsafe { BitBand::new(word as *const AtomicU32 as *mut u32, bit) }
+    }
+}
@@ -0,0 +1,41 @@
+//! # Boards
+//! The board is selected with a cargo feature, the teensy 3.2 is used when none is given:
+//! ```toml
+//! [dependencies.teensy]
+//! git = "https://github.com/irevoire/teensy.git"
+//! features = ["teensy_36"]
+//! ```
+//!
+//! The board provides its memory map (the `memory.x` file included by `layout.ld`, see
+//! `build.rs`), its pins (`port::Pins` and the names accepted by `make_pin!`) and its
+//! peripheral instances, like the UART3 and UART4 of the teensy 3.5 and 3.6.
+//!
+//! The MCUs also number their interrupts differently: `boot` has a vector table for each MCU
+//! and the board provides the vector numbers used by the drivers in its `vectors` module.
+//!
+//! The Teensy LC is not supported and has no feature: its MKL26Z64 is a cortex-m0+ with
+//! different peripherals, it would need its own crate.
+
+#[cfg(any(
+    all(feature = "teensy_30", feature = "teensy_31"),
+    all(feature = "teensy_30", feature = "teensy_32"),
+    all(feature = "teensy_30", feature = "teensy_35"),
+    all(feature = "teensy_30", feature = "teensy_36"),
+    all(feature = "teensy_31", feature = "teensy_32"),
+    all(feature = "teensy_31", feature = "teensy_35"),
+    all(feature = "teensy_31", feature = "teensy_36"),
+    all(feature = "teensy_32", feature = "teensy_35"),
+    all(feature = "teensy_32", feature = "teensy_36"),
+    all(feature = "teensy_35", feature = "teensy_36"),
+))]
+compile_error!("Only one board feature can be enabled");
+
+#[cfg(not(any(feature = "teensy_35", feature = "teensy_36")))]
+mod teensy32;
+#[cfg(not(any(feature = "teensy_35", feature = "teensy_36")))]
+pub use self::teensy32::*;
+
+#[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+mod teensy36;
+#[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+pub use self::teensy36::*;
@@ -0,0 +1,428 @@
+//! The Teensy 3.0, 3.1 and 3.2 share the same pinout, only the MCU changes:
+//! - 3.0: MK20DX128, 128K of flash and 16K of RAM
+//! - 3.1 and 3.2: MK20DX256, 256K of flash and 64K of RAM
+
+#[cfg(feature = "teensy_30")]
+pub const NAME: &str = "teensy 3.0";
+#[cfg(feature = "teensy_31")]
+pub const NAME: &str = "teensy 3.1";
+#[cfg(not(any(feature = "teensy_30", feature = "teensy_31")))]
+pub const NAME: &str = "teensy 3.2";
+
+/// The PLL multiplies the 16MHz crystal by `PLL_NUMERATOR / PLL_DENOMINATOR`, see `boot::init`.
+/// The MK20DX128 of the teensy 3.0 is a 50MHz part, it runs at 48MHz instead of 72MHz.
+#[cfg(feature = "teensy_30")]
+pub const PLL_NUMERATOR: u8 = 24;
+#[cfg(feature = "teensy_30")]
+pub const PLL_DENOMINATOR: u8 = 8;
+#[cfg(not(feature = "teensy_30"))]
+pub const PLL_NUMERATOR: u8 = 27;
+#[cfg(not(feature = "teensy_30"))]
+pub const PLL_DENOMINATOR: u8 = 6;
+
+/// The frequency of the core, the output of the PLL.
+#[cfg(feature = "teensy_30")]
+pub const F_CPU: u32 = 48_000_000;
+#[cfg(not(feature = "teensy_30"))]
+pub const F_CPU: u32 = 72_000_000;
+
+/// The core, bus and flash dividers of the PLL output, see `sim::Sim::set_dividers`. The bus
+/// and flash clocks are limited to 50MHz and 25MHz on every board.
+#[cfg(feature = "teensy_30")]
+pub const CLOCK_DIVIDERS: (u32, u32, u32) = (1, 1, 2);
+#[cfg(not(feature = "teensy_30"))]
+pub const CLOCK_DIVIDERS: (u32, u32, u32) = (1, 2, 3);
+
+/// The vectors of the MK20DX256 used by the drivers, see `interrupts::enable`. They must match
+/// the vector table of `boot`.
+#[cfg(not(feature = "teensy_30"))]
+pub mod vectors {
+    /// The size of the vector table, including the initial stack pointer
+    pub const COUNT: usize = 112;
+    pub const LLWU: usize = 37;
+    /// Shared by the watchdog and the EWM
+    pub const WDOG_EWM: usize = 38;
+    pub const RTC_ALARM: usize = 82;
+    pub const RTC_SECONDS: usize = 83;
+    /// The channels 1 to 3 follow
+    pub const PIT_CH0: usize = 84;
+    pub const TSI: usize = 99;
+    pub const LPTMR: usize = 101;
+    /// The ports B to E follow
+    pub const PORTA: usize = 103;
+}
+
+/// The vectors of the MK20DX128 used by the drivers, see `interrupts::enable`. They must match
+/// the vector table of `boot`.
+#[cfg(feature = "teensy_30")]
+pub mod vectors {
+    /// The size of the vector table, including the initial stack pointer
+    pub const COUNT: usize = 62;
+    pub const LLWU: usize = 25;
+    /// Shared by the watchdog and the EWM
+    pub const WDOG_EWM: usize = 26;
+    pub const RTC_ALARM: usize = 44;
+    pub const RTC_SECONDS: usize = 45;
+    /// The channels 1 to 3 follow
+    pub const PIT_CH0: usize = 46;
+    pub const TSI: usize = 53;
+    pub const LPTMR: usize = 55;
+    /// The ports B to E follow
+    pub const PORTA: usize = 56;
+}
+
+crate::port::pins::pins! {
+    0 => p0: PortB 16,
+    1 => p1: PortB 17,
+    2 => p2: PortD 0,
+    3 => p3: PortA 12,
+    4 => p4: PortA 13,
+    5 => p5: PortD 7,
+    6 => p6: PortD 4,
+    7 => p7: PortD 2,
+    8 => p8: PortD 3,
+    9 => p9: PortC 3,
+    10 => p10: PortC 4,
+    11 => p11: PortC 6,
+    12 => p12: PortC 7,
+    13 => p13: PortC 5,
+    14 => p14: PortD 1,
+    15 => p15: PortC 0,
+    16 => p16: PortB 0,
+    17 => p17: PortB 1,
+    18 => p18: PortB 3,
+    19 => p19: PortB 2,
+    20 => p20: PortD 5,
+    21 => p21: PortD 6,
+    22 => p22: PortC 1,
+    23 => p23: PortC 2,
+    24 => p24: PortA 5,
+    25 => p25: PortB 19,
+    26 => p26: PortE 1,
+    27 => p27: PortC 9,
+    28 => p28: PortC 8,
+    29 => p29: PortC 10,
+    30 => p30: PortC 11,
+    31 => p31: PortE 0,
+    32 => p32: PortB 18,
+    33 => p33: PortA 4,
+}
+
+/// The names of the pins of this board, used by `make_pin!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __board_pin {
+    // ===== analog pins =====
+    (A17) => {
+        $crate::make_pin!(@pin PortC 8)
+    };
+    (A16) => {
+        $crate::make_pin!(@pin PortC 9)
+    };
+    (A15) => {
+        $crate::make_pin!(@pin PortE 1)
+    };
+    (A18) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (A19) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    (A20) => {
+        $crate::make_pin!(@pin PortE 0)
+    };
+    (A9) => {
+        $crate::make_pin!(@pin PortC 2)
+    };
+    (A8) => {
+        $crate::make_pin!(@pin PortC 1)
+    };
+    (A7) => {
+        $crate::make_pin!(@pin PortD 6)
+    };
+    (A6) => {
+        $crate::make_pin!(@pin PortD 5)
+    };
+    (A5) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (A4) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (A3) => {
+        $crate::make_pin!(@pin PortB 1)
+    };
+    (A2) => {
+        $crate::make_pin!(@pin PortB 0)
+    };
+    (A1) => {
+        $crate::make_pin!(@pin PortC 0)
+    };
+    (A0) => {
+        $crate::make_pin!(@pin PortD 1)
+    };
+    // ===== I2C port =====
+    (SCL0) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (SDA0) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (SCL1) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (SDA1) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    // ===== serial port =====
+    (RX) => {
+        $crate::make_pin!(@pin PortA 12)
+    };
+    (TX) => {
+        $crate::make_pin!(@pin PortA 13)
+    };
+    (RX1) => {
+        $crate::make_pin!(@pin PortB 16)
+    };
+    (TX1) => {
+        $crate::make_pin!(@pin PortB 17)
+    };
+    (RX2) => {
+        $crate::make_pin!(@pin PortC 3)
+    };
+    (TX2) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (RX3) => {
+        $crate::make_pin!(@pin PortD 2)
+    };
+    (TX3) => {
+        $crate::make_pin!(@pin PortD 3)
+    };
+    // ===== SPI port =====
+    (CS) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (DOUT) => {
+        $crate::make_pin!(@pin PortC 6)
+    };
+    (DIN) => {
+        $crate::make_pin!(@pin PortC 7)
+    };
+    (SCK) => {
+        $crate::make_pin!(@pin PortC 5)
+    };
+    // ===== Schematic view =====
+    (PTA4) => {
+        $crate::make_pin!(@pin PortA 4)
+    };
+    (PTB18) => {
+        $crate::make_pin!(@pin PortB 18)
+    };
+    (PTE0) => {
+        $crate::make_pin!(@pin PortE 0)
+    };
+    (PTC11) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    (PTC10) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (PTC8) => {
+        $crate::make_pin!(@pin PortC 8)
+    };
+    (PTC9) => {
+        $crate::make_pin!(@pin PortC 9)
+    };
+    (PTE1) => {
+        $crate::make_pin!(@pin PortE 1)
+    };
+    (PTB19) => {
+        $crate::make_pin!(@pin PortB 19)
+    };
+    (PTA5) => {
+        $crate::make_pin!(@pin PortA 5)
+    };
+    (PTC2) => {
+        $crate::make_pin!(@pin PortC 2)
+    };
+    (PTC1) => {
+        $crate::make_pin!(@pin PortC 1)
+    };
+    (PTD6) => {
+        $crate::make_pin!(@pin PortD 6)
+    };
+    (PTD5) => {
+        $crate::make_pin!(@pin PortD 5)
+    };
+    (PTB2) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (PTB3) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (PTB1) => {
+        $crate::make_pin!(@pin PortB 1)
+    };
+    (PTB0) => {
+        $crate::make_pin!(@pin PortB 0)
+    };
+    (PTC0) => {
+        $crate::make_pin!(@pin PortC 0)
+    };
+    (PTD1) => {
+        $crate::make_pin!(@pin PortD 1)
+    };
+    (PTC5) => {
+        $crate::make_pin!(@pin PortC 5)
+    };
+    (PTC7) => {
+        $crate::make_pin!(@pin PortC 7)
+    };
+    (PTC6) => {
+        $crate::make_pin!(@pin PortC 6)
+    };
+    (PTC4) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (PTC3) => {
+        $crate::make_pin!(@pin PortC 3)
+    };
+    (PTD3) => {
+        $crate::make_pin!(@pin PortD 3)
+    };
+    (PTD2) => {
+        $crate::make_pin!(@pin PortD 2)
+    };
+    (PTD4) => {
+        $crate::make_pin!(@pin PortD 4)
+    };
+    (PTD7) => {
+        $crate::make_pin!(@pin PortD 7)
+    };
+    (PTA13) => {
+        $crate::make_pin!(@pin PortA 13)
+    };
+    (PTA12) => {
+        $crate::make_pin!(@pin PortA 12)
+    };
+    (PTD0) => {
+        $crate::make_pin!(@pin PortD 0)
+    };
+    (PTB17) => {
+        $crate::make_pin!(@pin PortB 17)
+    };
+    (PTB16) => {
+        $crate::make_pin!(@pin PortB 16)
+    };
+    // ===== digital pins =====
+    (0) => {
+        $crate::make_pin!(@pin PortB 16)
+    };
+    (1) => {
+        $crate::make_pin!(@pin PortB 17)
+    };
+    (2) => {
+        $crate::make_pin!(@pin PortD 0)
+    };
+    (3) => {
+        $crate::make_pin!(@pin PortA 12)
+    };
+    (4) => {
+        $crate::make_pin!(@pin PortA 13)
+    };
+    (5) => {
+        $crate::make_pin!(@pin PortD 7)
+    };
+    (6) => {
+        $crate::make_pin!(@pin PortD 4)
+    };
+    (7) => {
+        $crate::make_pin!(@pin PortD 2)
+    };
+    (8) => {
+        $crate::make_pin!(@pin PortD 3)
+    };
+    (9) => {
+        $crate::make_pin!(@pin PortC 3)
+    };
+    (10) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (11) => {
+        $crate::make_pin!(@pin PortC 6)
+    };
+    (12) => {
+        $crate::make_pin!(@pin PortC 7)
+    };
+    (13) => {
+        $crate::make_pin!(@pin PortC 5)
+    };
+    (14) => {
+        $crate::make_pin!(@pin PortD 1)
+    };
+    (15) => {
+        $crate::make_pin!(@pin PortC 0)
+    };
+    (16) => {
+        $crate::make_pin!(@pin PortB 0)
+    };
+    (17) => {
+        $crate::make_pin!(@pin PortB 1)
+    };
+    (18) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (19) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (20) => {
+        $crate::make_pin!(@pin PortD 5)
+    };
+    (21) => {
+        $crate::make_pin!(@pin PortD 6)
+    };
+    (22) => {
+        $crate::make_pin!(@pin PortC 1)
+    };
+    (23) => {
+        $crate::make_pin!(@pin PortC 2)
+    };
+    (24) => {
+        $crate::make_pin!(@pin PortA 5)
+    };
+    (25) => {
+        $crate::make_pin!(@pin PortB 19)
+    };
+    (26) => {
+        $crate::make_pin!(@pin PortE 1)
+    };
+    (27) => {
+        $crate::make_pin!(@pin PortC 9)
+    };
+    (28) => {
+        $crate::make_pin!(@pin PortC 8)
+    };
+    (29) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (30) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    (31) => {
+        $crate::make_pin!(@pin PortE 0)
+    };
+    (32) => {
+        $crate::make_pin!(@pin PortB 18)
+    };
+    (33) => {
+        $crate::make_pin!(@pin PortA 4)
+    };
+    ($other:tt) => {
+        compile_error!(concat!(
+            "make_pin!: unknown pin `",
+            stringify!($other),
+            "` for the teensy 3.0, 3.1 and 3.2"
+        ))
+    };
+}
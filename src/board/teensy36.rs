@@ -0,0 +1,643 @@
+//! The Teensy 3.5 and 3.6 share the same pinout, only the MCU changes:
+//! - 3.5: MK64FX512, 512K of flash and 192K of RAM
+//! - 3.6: MK66FX1M0, 1M of flash and 256K of RAM
+//!
+//! The pins 58 to 63 are wired to the micro SD socket.
+
+#[cfg(feature = "teensy_35")]
+pub const NAME: &str = "teensy 3.5";
+#[cfg(feature = "teensy_36")]
+pub const NAME: &str = "teensy 3.6";
+
+/// The PLL multiplies the 16MHz crystal by `PLL_NUMERATOR / PLL_DENOMINATOR`, see `boot::init`.
+/// On the MK66 the output of the PLL is also divided by two.
+#[cfg(feature = "teensy_35")]
+pub const PLL_NUMERATOR: u8 = 27;
+#[cfg(feature = "teensy_35")]
+pub const PLL_DENOMINATOR: u8 = 6;
+#[cfg(feature = "teensy_36")]
+pub const PLL_NUMERATOR: u8 = 18;
+#[cfg(feature = "teensy_36")]
+pub const PLL_DENOMINATOR: u8 = 2;
+
+/// The frequency of the core, the output of the PLL.
+pub const F_CPU: u32 = 72_000_000;
+
+/// The core, bus and flash dividers of the PLL output, see `sim::Sim::set_dividers`.
+pub const CLOCK_DIVIDERS: (u32, u32, u32) = (1, 2, 3);
+
+/// The vectors of the MK64FX512 and MK66FX1M0 used by the drivers, see `interrupts::enable`.
+/// They must match the vector table of `boot`.
+pub mod vectors {
+    /// The size of the vector table of the MK66, including the initial stack pointer. The MK64
+    /// has 14 vectors less, at the end.
+    pub const COUNT: usize = 116;
+    /// Shared by the watchdog and the EWM
+    pub const WDOG_EWM: usize = 38;
+    pub const RTC_ALARM: usize = 62;
+    pub const RTC_SECONDS: usize = 63;
+    /// The channels 1 to 3 follow
+    pub const PIT_CH0: usize = 64;
+    pub const LPTMR: usize = 74;
+    /// The ports B to E follow
+    pub const PORTA: usize = 75;
+}
+
+crate::port::pins::pins! {
+    0 => p0: PortB 16,
+    1 => p1: PortB 17,
+    2 => p2: PortD 0,
+    3 => p3: PortA 12,
+    4 => p4: PortA 13,
+    5 => p5: PortD 7,
+    6 => p6: PortD 4,
+    7 => p7: PortD 2,
+    8 => p8: PortD 3,
+    9 => p9: PortC 3,
+    10 => p10: PortC 4,
+    11 => p11: PortC 6,
+    12 => p12: PortC 7,
+    13 => p13: PortC 5,
+    14 => p14: PortD 1,
+    15 => p15: PortC 0,
+    16 => p16: PortB 0,
+    17 => p17: PortB 1,
+    18 => p18: PortB 3,
+    19 => p19: PortB 2,
+    20 => p20: PortD 5,
+    21 => p21: PortD 6,
+    22 => p22: PortC 1,
+    23 => p23: PortC 2,
+    24 => p24: PortE 26,
+    25 => p25: PortA 5,
+    26 => p26: PortA 14,
+    27 => p27: PortA 15,
+    28 => p28: PortA 16,
+    29 => p29: PortB 18,
+    30 => p30: PortB 19,
+    31 => p31: PortB 10,
+    32 => p32: PortB 11,
+    33 => p33: PortE 24,
+    34 => p34: PortE 25,
+    35 => p35: PortC 8,
+    36 => p36: PortC 9,
+    37 => p37: PortC 10,
+    38 => p38: PortC 11,
+    39 => p39: PortA 17,
+    40 => p40: PortA 28,
+    41 => p41: PortA 29,
+    42 => p42: PortA 26,
+    43 => p43: PortB 20,
+    44 => p44: PortB 22,
+    45 => p45: PortB 23,
+    46 => p46: PortB 21,
+    47 => p47: PortD 8,
+    48 => p48: PortD 9,
+    49 => p49: PortB 4,
+    50 => p50: PortB 5,
+    51 => p51: PortD 14,
+    52 => p52: PortD 13,
+    53 => p53: PortD 12,
+    54 => p54: PortD 15,
+    55 => p55: PortD 11,
+    56 => p56: PortE 10,
+    57 => p57: PortE 11,
+    58 => p58: PortE 0,
+    59 => p59: PortE 1,
+    60 => p60: PortE 2,
+    61 => p61: PortE 3,
+    62 => p62: PortE 4,
+    63 => p63: PortE 5,
+}
+
+/// The names of the pins of this board, used by `make_pin!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __board_pin {
+    // ===== analog pins =====
+    (A0) => {
+        $crate::make_pin!(@pin PortD 1)
+    };
+    (A1) => {
+        $crate::make_pin!(@pin PortC 0)
+    };
+    (A2) => {
+        $crate::make_pin!(@pin PortB 0)
+    };
+    (A3) => {
+        $crate::make_pin!(@pin PortB 1)
+    };
+    (A4) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (A5) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (A6) => {
+        $crate::make_pin!(@pin PortD 5)
+    };
+    (A7) => {
+        $crate::make_pin!(@pin PortD 6)
+    };
+    (A8) => {
+        $crate::make_pin!(@pin PortC 1)
+    };
+    (A9) => {
+        $crate::make_pin!(@pin PortC 2)
+    };
+    (A12) => {
+        $crate::make_pin!(@pin PortB 10)
+    };
+    (A13) => {
+        $crate::make_pin!(@pin PortB 11)
+    };
+    (A14) => {
+        $crate::make_pin!(@pin PortE 24)
+    };
+    (A15) => {
+        $crate::make_pin!(@pin PortE 25)
+    };
+    (A16) => {
+        $crate::make_pin!(@pin PortC 8)
+    };
+    (A17) => {
+        $crate::make_pin!(@pin PortC 9)
+    };
+    (A18) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (A19) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    (A20) => {
+        $crate::make_pin!(@pin PortA 17)
+    };
+    (A23) => {
+        $crate::make_pin!(@pin PortB 4)
+    };
+    (A24) => {
+        $crate::make_pin!(@pin PortB 5)
+    };
+    // ===== I2C port =====
+    (SCL0) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (SDA0) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (SCL1) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (SDA1) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    (SCL2) => {
+        $crate::make_pin!(@pin PortA 12)
+    };
+    (SDA2) => {
+        $crate::make_pin!(@pin PortA 13)
+    };
+    // ===== serial port =====
+    (RX1) => {
+        $crate::make_pin!(@pin PortB 16)
+    };
+    (TX1) => {
+        $crate::make_pin!(@pin PortB 17)
+    };
+    (RX2) => {
+        $crate::make_pin!(@pin PortC 3)
+    };
+    (TX2) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (RX3) => {
+        $crate::make_pin!(@pin PortD 2)
+    };
+    (TX3) => {
+        $crate::make_pin!(@pin PortD 3)
+    };
+    (RX4) => {
+        $crate::make_pin!(@pin PortB 10)
+    };
+    (TX4) => {
+        $crate::make_pin!(@pin PortB 11)
+    };
+    (RX5) => {
+        $crate::make_pin!(@pin PortE 25)
+    };
+    (TX5) => {
+        $crate::make_pin!(@pin PortE 24)
+    };
+    (RX6) => {
+        $crate::make_pin!(@pin PortD 8)
+    };
+    (TX6) => {
+        $crate::make_pin!(@pin PortD 9)
+    };
+    // ===== SPI port =====
+    (CS) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (DOUT) => {
+        $crate::make_pin!(@pin PortC 6)
+    };
+    (DIN) => {
+        $crate::make_pin!(@pin PortC 7)
+    };
+    (SCK) => {
+        $crate::make_pin!(@pin PortC 5)
+    };
+    // ===== Schematic view =====
+    (PTE5) => {
+        $crate::make_pin!(@pin PortE 5)
+    };
+    (PTE4) => {
+        $crate::make_pin!(@pin PortE 4)
+    };
+    (PTE3) => {
+        $crate::make_pin!(@pin PortE 3)
+    };
+    (PTE2) => {
+        $crate::make_pin!(@pin PortE 2)
+    };
+    (PTE1) => {
+        $crate::make_pin!(@pin PortE 1)
+    };
+    (PTE0) => {
+        $crate::make_pin!(@pin PortE 0)
+    };
+    (PTE11) => {
+        $crate::make_pin!(@pin PortE 11)
+    };
+    (PTE10) => {
+        $crate::make_pin!(@pin PortE 10)
+    };
+    (PTD11) => {
+        $crate::make_pin!(@pin PortD 11)
+    };
+    (PTD15) => {
+        $crate::make_pin!(@pin PortD 15)
+    };
+    (PTD12) => {
+        $crate::make_pin!(@pin PortD 12)
+    };
+    (PTD13) => {
+        $crate::make_pin!(@pin PortD 13)
+    };
+    (PTD14) => {
+        $crate::make_pin!(@pin PortD 14)
+    };
+    (PTB5) => {
+        $crate::make_pin!(@pin PortB 5)
+    };
+    (PTB4) => {
+        $crate::make_pin!(@pin PortB 4)
+    };
+    (PTD9) => {
+        $crate::make_pin!(@pin PortD 9)
+    };
+    (PTD8) => {
+        $crate::make_pin!(@pin PortD 8)
+    };
+    (PTB21) => {
+        $crate::make_pin!(@pin PortB 21)
+    };
+    (PTB23) => {
+        $crate::make_pin!(@pin PortB 23)
+    };
+    (PTB22) => {
+        $crate::make_pin!(@pin PortB 22)
+    };
+    (PTB20) => {
+        $crate::make_pin!(@pin PortB 20)
+    };
+    (PTA26) => {
+        $crate::make_pin!(@pin PortA 26)
+    };
+    (PTA29) => {
+        $crate::make_pin!(@pin PortA 29)
+    };
+    (PTA28) => {
+        $crate::make_pin!(@pin PortA 28)
+    };
+    (PTA17) => {
+        $crate::make_pin!(@pin PortA 17)
+    };
+    (PTC11) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    (PTC10) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (PTC9) => {
+        $crate::make_pin!(@pin PortC 9)
+    };
+    (PTC8) => {
+        $crate::make_pin!(@pin PortC 8)
+    };
+    (PTE25) => {
+        $crate::make_pin!(@pin PortE 25)
+    };
+    (PTE24) => {
+        $crate::make_pin!(@pin PortE 24)
+    };
+    (PTB11) => {
+        $crate::make_pin!(@pin PortB 11)
+    };
+    (PTB10) => {
+        $crate::make_pin!(@pin PortB 10)
+    };
+    (PTB19) => {
+        $crate::make_pin!(@pin PortB 19)
+    };
+    (PTB18) => {
+        $crate::make_pin!(@pin PortB 18)
+    };
+    (PTA16) => {
+        $crate::make_pin!(@pin PortA 16)
+    };
+    (PTA15) => {
+        $crate::make_pin!(@pin PortA 15)
+    };
+    (PTA14) => {
+        $crate::make_pin!(@pin PortA 14)
+    };
+    (PTA5) => {
+        $crate::make_pin!(@pin PortA 5)
+    };
+    (PTE26) => {
+        $crate::make_pin!(@pin PortE 26)
+    };
+    (PTC2) => {
+        $crate::make_pin!(@pin PortC 2)
+    };
+    (PTC1) => {
+        $crate::make_pin!(@pin PortC 1)
+    };
+    (PTD6) => {
+        $crate::make_pin!(@pin PortD 6)
+    };
+    (PTD5) => {
+        $crate::make_pin!(@pin PortD 5)
+    };
+    (PTB2) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (PTB3) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (PTB1) => {
+        $crate::make_pin!(@pin PortB 1)
+    };
+    (PTB0) => {
+        $crate::make_pin!(@pin PortB 0)
+    };
+    (PTC0) => {
+        $crate::make_pin!(@pin PortC 0)
+    };
+    (PTD1) => {
+        $crate::make_pin!(@pin PortD 1)
+    };
+    (PTC5) => {
+        $crate::make_pin!(@pin PortC 5)
+    };
+    (PTC7) => {
+        $crate::make_pin!(@pin PortC 7)
+    };
+    (PTC6) => {
+        $crate::make_pin!(@pin PortC 6)
+    };
+    (PTC4) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (PTC3) => {
+        $crate::make_pin!(@pin PortC 3)
+    };
+    (PTD3) => {
+        $crate::make_pin!(@pin PortD 3)
+    };
+    (PTD2) => {
+        $crate::make_pin!(@pin PortD 2)
+    };
+    (PTD4) => {
+        $crate::make_pin!(@pin PortD 4)
+    };
+    (PTD7) => {
+        $crate::make_pin!(@pin PortD 7)
+    };
+    (PTA13) => {
+        $crate::make_pin!(@pin PortA 13)
+    };
+    (PTA12) => {
+        $crate::make_pin!(@pin PortA 12)
+    };
+    (PTD0) => {
+        $crate::make_pin!(@pin PortD 0)
+    };
+    (PTB17) => {
+        $crate::make_pin!(@pin PortB 17)
+    };
+    (PTB16) => {
+        $crate::make_pin!(@pin PortB 16)
+    };
+    // ===== digital pins =====
+    (0) => {
+        $crate::make_pin!(@pin PortB 16)
+    };
+    (1) => {
+        $crate::make_pin!(@pin PortB 17)
+    };
+    (2) => {
+        $crate::make_pin!(@pin PortD 0)
+    };
+    (3) => {
+        $crate::make_pin!(@pin PortA 12)
+    };
+    (4) => {
+        $crate::make_pin!(@pin PortA 13)
+    };
+    (5) => {
+        $crate::make_pin!(@pin PortD 7)
+    };
+    (6) => {
+        $crate::make_pin!(@pin PortD 4)
+    };
+    (7) => {
+        $crate::make_pin!(@pin PortD 2)
+    };
+    (8) => {
+        $crate::make_pin!(@pin PortD 3)
+    };
+    (9) => {
+        $crate::make_pin!(@pin PortC 3)
+    };
+    (10) => {
+        $crate::make_pin!(@pin PortC 4)
+    };
+    (11) => {
+        $crate::make_pin!(@pin PortC 6)
+    };
+    (12) => {
+        $crate::make_pin!(@pin PortC 7)
+    };
+    (13) => {
+        $crate::make_pin!(@pin PortC 5)
+    };
+    (14) => {
+        $crate::make_pin!(@pin PortD 1)
+    };
+    (15) => {
+        $crate::make_pin!(@pin PortC 0)
+    };
+    (16) => {
+        $crate::make_pin!(@pin PortB 0)
+    };
+    (17) => {
+        $crate::make_pin!(@pin PortB 1)
+    };
+    (18) => {
+        $crate::make_pin!(@pin PortB 3)
+    };
+    (19) => {
+        $crate::make_pin!(@pin PortB 2)
+    };
+    (20) => {
+        $crate::make_pin!(@pin PortD 5)
+    };
+    (21) => {
+        $crate::make_pin!(@pin PortD 6)
+    };
+    (22) => {
+        $crate::make_pin!(@pin PortC 1)
+    };
+    (23) => {
+        $crate::make_pin!(@pin PortC 2)
+    };
+    (24) => {
+        $crate::make_pin!(@pin PortE 26)
+    };
+    (25) => {
+        $crate::make_pin!(@pin PortA 5)
+    };
+    (26) => {
+        $crate::make_pin!(@pin PortA 14)
+    };
+    (27) => {
+        $crate::make_pin!(@pin PortA 15)
+    };
+    (28) => {
+        $crate::make_pin!(@pin PortA 16)
+    };
+    (29) => {
+        $crate::make_pin!(@pin PortB 18)
+    };
+    (30) => {
+        $crate::make_pin!(@pin PortB 19)
+    };
+    (31) => {
+        $crate::make_pin!(@pin PortB 10)
+    };
+    (32) => {
+        $crate::make_pin!(@pin PortB 11)
+    };
+    (33) => {
+        $crate::make_pin!(@pin PortE 24)
+    };
+    (34) => {
+        $crate::make_pin!(@pin PortE 25)
+    };
+    (35) => {
+        $crate::make_pin!(@pin PortC 8)
+    };
+    (36) => {
+        $crate::make_pin!(@pin PortC 9)
+    };
+    (37) => {
+        $crate::make_pin!(@pin PortC 10)
+    };
+    (38) => {
+        $crate::make_pin!(@pin PortC 11)
+    };
+    (39) => {
+        $crate::make_pin!(@pin PortA 17)
+    };
+    (40) => {
+        $crate::make_pin!(@pin PortA 28)
+    };
+    (41) => {
+        $crate::make_pin!(@pin PortA 29)
+    };
+    (42) => {
+        $crate::make_pin!(@pin PortA 26)
+    };
+    (43) => {
+        $crate::make_pin!(@pin PortB 20)
+    };
+    (44) => {
+        $crate::make_pin!(@pin PortB 22)
+    };
+    (45) => {
+        $crate::make_pin!(@pin PortB 23)
+    };
+    (46) => {
+        $crate::make_pin!(@pin PortB 21)
+    };
+    (47) => {
+        $crate::make_pin!(@pin PortD 8)
+    };
+    (48) => {
+        $crate::make_pin!(@pin PortD 9)
+    };
+    (49) => {
+        $crate::make_pin!(@pin PortB 4)
+    };
+    (50) => {
+        $crate::make_pin!(@pin PortB 5)
+    };
+    (51) => {
+        $crate::make_pin!(@pin PortD 14)
+    };
+    (52) => {
+        $crate::make_pin!(@pin PortD 13)
+    };
+    (53) => {
+        $crate::make_pin!(@pin PortD 12)
+    };
+    (54) => {
+        $crate::make_pin!(@pin PortD 15)
+    };
+    (55) => {
+        $crate::make_pin!(@pin PortD 11)
+    };
+    (56) => {
+        $crate::make_pin!(@pin PortE 10)
+    };
+    (57) => {
+        $crate::make_pin!(@pin PortE 11)
+    };
+    (58) => {
+        $crate::make_pin!(@pin PortE 0)
+    };
+    (59) => {
+        $crate::make_pin!(@pin PortE 1)
+    };
+    (60) => {
+        $crate::make_pin!(@pin PortE 2)
+    };
+    (61) => {
+        $crate::make_pin!(@pin PortE 3)
+    };
+    (62) => {
+        $crate::make_pin!(@pin PortE 4)
+    };
+    (63) => {
+        $crate::make_pin!(@pin PortE 5)
+    };
+    ($other:tt) => {
+        compile_error!(concat!(
+            "make_pin!: unknown pin `",
+            stringify!($other),
+            "` for the teensy 3.5 and 3.6"
+        ))
+    };
+}
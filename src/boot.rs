@@ -1,6 +1,6 @@
 //! Here is the most important file of this crate.
 //! When you add this crate as a dependency it will move the bootloader to the good section. Enable
-//! **all** the port, the clock at 72MHz and disable the watchdog and **then** call your `main`.
+//! **all** the port, the clock at 72MHz (48MHz on the teensy 3.0) and disable the watchdog and **then** call your `main`.
 //! Right now if you don't want something you'll need to either disable it in your `main` or edit
 //! this crate.
 
@@ -11,18 +11,57 @@ use crate::*;
 /// - Core: 72MHz
 /// - Peripherals: 36MHz
 /// - Flash: 24MHz
+/// The teensy 3.0 runs at 48MHz, with a 48MHz bus and a 24MHz flash, see `board::F_CPU`.
 /// Enable all the ports clock gate. This may consume more power than what you need if you don't
 /// use all the ports.
-/// Disable the watchdog.
+/// Disable the watchdog, `watchdog::Watchdog::configure` can enable it again from your `main`.
+/// Start the SysTick to provide the `time` module.
 #[no_mangle]
 extern "C" fn __boot() {
     unsafe {
+        init_ram();
         init();
         main();
     }
     core::panic!("Came out of main");
 }
 
+// Symbols defined in the linker script
+extern "C" {
+    static mut _sdata: u32;
+    static mut _edata: u32;
+    static _sidata: u32;
+    static mut _sbss: u32;
+    static mut _ebss: u32;
+    static mut _sbitband: u32;
+    static mut _ebitband: u32;
+}
+
+/// Copy the initial value of the statics from the flash to the RAM and zero the others, including
+/// the ones of the `.bitband` section.
+/// Nothing using a static can run before this function.
+unsafe fn init_ram() {
+    let mut data = &mut _sdata as *mut u32;
+    let mut idata = &_sidata as *const u32;
+    while data < &mut _edata as *mut u32 {
+        core::ptr::write_volatile(data, core::ptr::read(idata));
+        data = data.offset(1);
+        idata = idata.offset(1);
+    }
+
+    let mut bss = &mut _sbss as *mut u32;
+    while bss < &mut _ebss as *mut u32 {
+        core::ptr::write_volatile(bss, 0);
+        bss = bss.offset(1);
+    }
+
+    let mut bitband = &mut _sbitband as *mut u32;
+    while bitband < &mut _ebitband as *mut u32 {
+        core::ptr::write_volatile(bitband, 0);
+        bitband = bitband.offset(1);
+    }
+}
+
 #[cfg(not(feature = "manual_init"))]
 #[no_mangle]
 fn init() {
@@ -38,7 +77,7 @@ fn init() {
     wdog.disable();
 
     // Enable the crystal oscillator with 10pf of capacitance
-    osc.enable(10);
+    osc.enable(10).unwrap();
     // Turn on all the port clock gate
     sim.enable_clock(sim::Clock::PortA);
     sim.enable_clock(sim::Clock::PortB);
@@ -46,11 +85,12 @@ fn init() {
     sim.enable_clock(sim::Clock::PortD);
     sim.enable_clock(sim::Clock::PortE);
 
-    // Set our clocks:
+    // Set our clocks, on the teensy 3.2:
     // core: 72Mhz
     // peripheral: 36MHz
     // flash: 24MHz
-    sim.set_dividers(1, 2, 3);
+    let (core, bus, flash) = board::CLOCK_DIVIDERS;
+    sim.set_dividers(core, bus, flash);
     // We would also set the USB divider here if we wanted to use it.
 
     // Now we can start setting up the MCG for our needs.
@@ -60,18 +100,22 @@ fn init() {
         fei.enable_xtal(mcg::OscRange::VeryHigh);
         let fbe = fei.use_external(512);
 
-        // PLL is 27/6 * xtal == 72MHz
-        let pbe = fbe.enable_pll(27, 6);
+        // PLL is `board::F_CPU`, the factors depend on the MCU of the board
+        let pbe = fbe.enable_pll(board::PLL_NUMERATOR, board::PLL_DENOMINATOR);
         pbe.use_pll();
     } else {
         panic!("Somehow the clock wasn't in FEI mode");
     }
+
+    // Start the millisecond tick used by `time`
+    time::start(mcg::F_CPU);
 }
 
-/// This is the Interrupt Descriptor Table
+/// This is the Interrupt Descriptor Table of the MK20DX256 (teensy 3.1 and 3.2)
+#[cfg(not(any(feature = "teensy_30", feature = "teensy_35", feature = "teensy_36")))]
 #[link_section = ".vector_table.interrupts"]
 #[no_mangle]
-pub static _INTERRUPTS: [unsafe extern "C" fn(); 111] = [
+pub static _INTERRUPTS: [unsafe extern "C" fn(); board::vectors::COUNT - 1] = [
     __boot, // TODO: Move this to a different vector?
     interrupts::isr_non_maskable,
     interrupts::isr_hard_fault,
@@ -185,6 +229,197 @@ pub static _INTERRUPTS: [unsafe extern "C" fn(); 111] = [
     interrupts::isr_ignore_no_args, // Reserved 111
 ];
 
+/// This is the Interrupt Descriptor Table of the MK20DX128 (teensy 3.0)
+#[cfg(feature = "teensy_30")]
+#[link_section = ".vector_table.interrupts"]
+#[no_mangle]
+pub static _INTERRUPTS: [unsafe extern "C" fn(); board::vectors::COUNT - 1] = [
+    __boot, // TODO: Move this to a different vector?
+    interrupts::isr_non_maskable,
+    interrupts::isr_hard_fault,
+    interrupts::isr_memmanage_fault,
+    interrupts::isr_bus_fault,
+    interrupts::isr_usage_fault,
+    interrupts::isr_ignore_no_args, // Reserved 7
+    interrupts::isr_ignore_no_args, // Reserved 8
+    interrupts::isr_ignore_no_args, // Reserved 9
+    interrupts::isr_ignore_no_args, // Reserved 10
+    interrupts::isr_svcall,
+    interrupts::isr_debug_monitor,
+    interrupts::isr_ignore_no_args, // Reserved 13
+    interrupts::isr_pendablesrvreq,
+    interrupts::isr_systick,
+    interrupts::isr_dma_ch0_complete,
+    interrupts::isr_dma_ch1_complete,
+    interrupts::isr_dma_ch2_complete,
+    interrupts::isr_dma_ch3_complete,
+    interrupts::isr_dma_error,
+    interrupts::isr_ignore_no_args, // Reserved 21
+    interrupts::isr_flash_cmd_complete,
+    interrupts::isr_flash_read_collision,
+    interrupts::isr_low_voltage_warning,
+    interrupts::isr_low_voltage_wakeup,
+    interrupts::isr_wdog_or_emw,
+    interrupts::isr_i2c0,
+    interrupts::isr_spi0,
+    interrupts::isr_i2s0_transmit,
+    interrupts::isr_i2s0_receive,
+    interrupts::isr_uart0_lon,
+    interrupts::isr_uart0_status,
+    interrupts::isr_uart0_error,
+    interrupts::isr_uart1_status,
+    interrupts::isr_uart1_error,
+    interrupts::isr_uart2_status,
+    interrupts::isr_uart2_error,
+    interrupts::isr_adc0,
+    interrupts::isr_cmp0,
+    interrupts::isr_cmp1,
+    interrupts::isr_ftm0,
+    interrupts::isr_ftm1,
+    interrupts::isr_cmt,
+    interrupts::isr_rtc_alarm,
+    interrupts::isr_rtc_seconds,
+    interrupts::isr_pit_ch0,
+    interrupts::isr_pit_ch1,
+    interrupts::isr_pit_ch2,
+    interrupts::isr_pit_ch3,
+    interrupts::isr_pdb,
+    interrupts::isr_usb_otg,
+    interrupts::isr_usb_charger,
+    interrupts::isr_tsi,
+    interrupts::isr_mcg,
+    interrupts::isr_lpt,
+    interrupts::isr_port_a,
+    interrupts::isr_port_b,
+    interrupts::isr_port_c,
+    interrupts::isr_port_d,
+    interrupts::isr_port_e,
+    interrupts::isr_software,
+];
+
+/// This is the Interrupt Descriptor Table of the MK64FX512 (teensy 3.5) and MK66FX1M0 (teensy
+/// 3.6). The vectors after the ENET ones only exist on the MK66.
+#[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+#[link_section = ".vector_table.interrupts"]
+#[no_mangle]
+pub static _INTERRUPTS: [unsafe extern "C" fn(); board::vectors::COUNT - 1] = [
+    __boot, // TODO: Move this to a different vector?
+    interrupts::isr_non_maskable,
+    interrupts::isr_hard_fault,
+    interrupts::isr_memmanage_fault,
+    interrupts::isr_bus_fault,
+    interrupts::isr_usage_fault,
+    interrupts::isr_ignore_no_args, // Reserved 7
+    interrupts::isr_ignore_no_args, // Reserved 8
+    interrupts::isr_ignore_no_args, // Reserved 9
+    interrupts::isr_ignore_no_args, // Reserved 10
+    interrupts::isr_svcall,
+    interrupts::isr_debug_monitor,
+    interrupts::isr_ignore_no_args, // Reserved 13
+    interrupts::isr_pendablesrvreq,
+    interrupts::isr_systick,
+    interrupts::isr_dma_ch0_complete,
+    interrupts::isr_dma_ch1_complete,
+    interrupts::isr_dma_ch2_complete,
+    interrupts::isr_dma_ch3_complete,
+    interrupts::isr_dma_ch4_complete,
+    interrupts::isr_dma_ch5_complete,
+    interrupts::isr_dma_ch6_complete,
+    interrupts::isr_dma_ch7_complete,
+    interrupts::isr_dma_ch8_complete,
+    interrupts::isr_dma_ch9_complete,
+    interrupts::isr_dma_ch10_complete,
+    interrupts::isr_dma_ch11_complete,
+    interrupts::isr_dma_ch12_complete,
+    interrupts::isr_dma_ch13_complete,
+    interrupts::isr_dma_ch14_complete,
+    interrupts::isr_dma_ch15_complete,
+    interrupts::isr_dma_error,
+    interrupts::isr_ignore_no_args, // MCM
+    interrupts::isr_flash_cmd_complete,
+    interrupts::isr_flash_read_collision,
+    interrupts::isr_low_voltage_warning,
+    interrupts::isr_low_voltage_wakeup,
+    interrupts::isr_wdog_or_emw,
+    interrupts::isr_ignore_no_args, // RNG
+    interrupts::isr_i2c0,
+    interrupts::isr_i2c1,
+    interrupts::isr_spi0,
+    interrupts::isr_spi1,
+    interrupts::isr_i2s0_transmit,
+    interrupts::isr_i2s0_receive,
+    interrupts::isr_uart0_lon,
+    interrupts::isr_uart0_status,
+    interrupts::isr_uart0_error,
+    interrupts::isr_uart1_status,
+    interrupts::isr_uart1_error,
+    interrupts::isr_uart2_status,
+    interrupts::isr_uart2_error,
+    interrupts::isr_ignore_no_args, // UART3 status
+    interrupts::isr_ignore_no_args, // UART3 error
+    interrupts::isr_adc0,
+    interrupts::isr_cmp0,
+    interrupts::isr_cmp1,
+    interrupts::isr_ftm0,
+    interrupts::isr_ftm1,
+    interrupts::isr_ftm2,
+    interrupts::isr_cmt,
+    interrupts::isr_rtc_alarm,
+    interrupts::isr_rtc_seconds,
+    interrupts::isr_pit_ch0,
+    interrupts::isr_pit_ch1,
+    interrupts::isr_pit_ch2,
+    interrupts::isr_pit_ch3,
+    interrupts::isr_pdb,
+    interrupts::isr_usb_otg,
+    interrupts::isr_usb_charger,
+    interrupts::isr_ignore_no_args, // Reserved 71
+    interrupts::isr_dac0,
+    interrupts::isr_mcg,
+    interrupts::isr_lpt,
+    interrupts::isr_port_a,
+    interrupts::isr_port_b,
+    interrupts::isr_port_c,
+    interrupts::isr_port_d,
+    interrupts::isr_port_e,
+    interrupts::isr_software,
+    interrupts::isr_ignore_no_args, // SPI2
+    interrupts::isr_ignore_no_args, // UART4 status
+    interrupts::isr_ignore_no_args, // UART4 error
+    interrupts::isr_ignore_no_args, // UART5 status
+    interrupts::isr_ignore_no_args, // UART5 error
+    interrupts::isr_cmp2,
+    interrupts::isr_ignore_no_args, // FTM3
+    interrupts::isr_ignore_no_args, // DAC1
+    interrupts::isr_adc1,
+    interrupts::isr_ignore_no_args, // I2C2
+    interrupts::isr_can0_or_msg_buf,
+    interrupts::isr_can0_bus_off,
+    interrupts::isr_can0_error,
+    interrupts::isr_can0_transmit_warn,
+    interrupts::isr_can0_receive_warn,
+    interrupts::isr_can0_wakeup,
+    interrupts::isr_ignore_no_args, // SDHC
+    interrupts::isr_ignore_no_args, // ENET 1588 timer
+    interrupts::isr_ignore_no_args, // ENET transmit
+    interrupts::isr_ignore_no_args, // ENET receive
+    interrupts::isr_ignore_no_args, // ENET error
+    interrupts::isr_ignore_no_args, // MK66 only, LPUART0
+    interrupts::isr_ignore_no_args, // MK66 only, TSI
+    interrupts::isr_ignore_no_args, // MK66 only, TPM1
+    interrupts::isr_ignore_no_args, // MK66 only, TPM2
+    interrupts::isr_ignore_no_args, // MK66 only, USBHS DCD
+    interrupts::isr_ignore_no_args, // MK66 only, I2C3
+    interrupts::isr_ignore_no_args, // MK66 only, CMP3
+    interrupts::isr_ignore_no_args, // MK66 only, USBHS
+    interrupts::isr_ignore_no_args, // MK66 only, CAN1
+    interrupts::isr_ignore_no_args, // MK66 only, CAN1
+    interrupts::isr_ignore_no_args, // MK66 only, CAN1
+    interrupts::isr_ignore_no_args, // MK66 only, CAN1
+    interrupts::isr_ignore_no_args, // MK66 only, CAN1
+    interrupts::isr_ignore_no_args, // MK66 only, CAN1
+];
+
 /// Flash configuration
 /// Controls how the flash can be read or written.
 ///  The Teensy bootloader makes assumptions about these values, so
@@ -0,0 +1,56 @@
+//! # Cycle counter
+//! The Data Watchpoint and Trace unit (DWT) of the cortex-m4 provides a 32 bits counter
+//! incremented on every core cycle. It wraps every 2^32 cycles, around 59 seconds at 72MHz.
+//! ```rust
+//! cycles::enable();
+//! let elapsed = cycles::measure(|| do_something());
+//! let ns = cycles::to_nanos(elapsed);
+//! ```
+
+/// Debug Exception and Monitor Control Register, TRCENA enables the DWT.
+const DEMCR: u32 = 0xE000_EDFC;
+const DWT_CTRL: u32 = 0xE000_1000;
+const DWT_CYCCNT: u32 = 0xE000_1004;
+
+/// Enable the trace unit and the cycle counter.
+pub fn enable() {
+    unsafe {
+        let demcr = core::ptr::read_volatile(DEMCR as *const u32);
+        core::ptr::write_volatile(DEMCR as *mut u32, demcr | 1 << 24);
+        let ctrl = core::ptr::read_volatile(DWT_CTRL as *const u32);
+        core::ptr::write_volatile(DWT_CTRL as *mut u32, ctrl | 1);
+    }
+}
+
+pub fn is_enabled() -> bool {
+    unsafe {
+        core::ptr::read_volatile(DEMCR as *const u32) & 1 << 24 != 0
+            && core::ptr::read_volatile(DWT_CTRL as *const u32) & 1 != 0
+    }
+}
+
+/// The current value of the cycle counter. Only the difference between two values is
+/// meaningful, use `wrapping_sub` to compute it.
+#[inline]
+pub fn now() -> u32 {
+    unsafe { core::ptr::read_volatile(DWT_CYCCNT as *const u32) }
+}
+
+/// Number of cycles spent executing `f`, including the interrupts that happened meanwhile.
+/// The cycle counter must be enabled.
+#[inline]
+pub fn measure<F: FnOnce()>(f: F) -> u32 {
+    let start = now();
+    f();
+    now().wrapping_sub(start)
+}
+
+/// Convert a number of cycles to nanoseconds, with the core running at `mcg::F_CPU`.
+pub fn to_nanos(cycles: u32) -> u64 {
+    cycles as u64 * 1_000_000_000 / crate::mcg::F_CPU as u64
+}
+
+/// Convert a number of nanoseconds to cycles, with the core running at `mcg::F_CPU`.
+pub fn from_nanos(nanos: u64) -> u64 {
+    nanos * crate::mcg::F_CPU as u64 / 1_000_000_000
+}
@@ -0,0 +1,168 @@
+//! # External Watchdog Monitor (EWM)
+//! The EWM is a second watchdog which, instead of resetting the chip, drives the EWM_OUT pin low
+//! when it is not refreshed in time. It is meant to put the external circuitry (motor drivers,
+//! heaters...) in a safe state independently of the CPU.
+//!
+//! The EWM counts the 1kHz LPO cycles since the last refresh:
+//! - a refresh before `window` is an error and asserts EWM_OUT,
+//! - a refresh between `window` and `timeout` restarts the counter,
+//! - reaching `timeout` asserts EWM_OUT.
+//!
+//! The EWM_IN pin can also assert EWM_OUT, to let an external supervisor force the safe state.
+//!
+//! **The control and compare registers can only be written once after a reset.**
+
+use crate::port::{Pin, PortName};
+use bit_field::BitField;
+use core::time::Duration;
+use volatile::Volatile;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The EWM was already configured since the last reset
+    AlreadyConfigured,
+    /// The timeout must be between 1ms and 254ms
+    InvalidTimeout,
+    /// The window must be shorter than the timeout
+    InvalidWindow,
+    /// This pin can't be routed to the EWM
+    InvalidPin,
+}
+
+/// Level of the EWM_IN pin asserting EWM_OUT.
+#[derive(Clone, Copy)]
+pub enum Polarity {
+    ActiveLow = 0,
+    ActiveHigh = 1,
+}
+
+#[derive(Clone, Copy)]
+pub struct EwmConfig {
+    /// Refreshing the EWM before this duration has elapsed asserts EWM_OUT
+    pub window: Duration,
+    /// Time after which EWM_OUT is asserted if the EWM was not refreshed
+    pub timeout: Duration,
+    /// Enable the EWM_IN pin with the given polarity
+    pub input: Option<Polarity>,
+    /// Trigger the EWM interrupt (see `set_interrupt_handler`) when EWM_OUT is asserted
+    pub interrupt: bool,
+}
+
+impl Default for EwmConfig {
+    fn default() -> Self {
+        EwmConfig {
+            window: Duration::from_millis(0),
+            timeout: Duration::from_millis(254),
+            input: None,
+            interrupt: false,
+        }
+    }
+}
+
+static mut INTERRUPT_HANDLER: Option<fn()> = None;
+
+#[repr(C, packed)]
+pub struct Ewm {
+    ctrl: Volatile<u8>,
+    serv: Volatile<u8>,
+    cmpl: Volatile<u8>,
+    cmph: Volatile<u8>,
+}
+
+impl Ewm {
+    /// The EWM clock gate (`sim::Clock::Ewm`) must be enabled before using it.
+    pub unsafe fn new() -> &'static mut Ewm {
+        &mut *(0x4006_1000 as *mut Ewm)
+    }
+
+    /// Configure and enable the EWM. This can only be done once per reset.
+    pub fn configure(&mut self, config: EwmConfig) -> Result<(), Error> {
+        if self.ctrl.read().get_bit(0) {
+            return Err(Error::AlreadyConfigured);
+        }
+
+        // The EWM is clocked by the 1kHz LPO
+        let timeout = config.timeout.as_millis();
+        let window = config.window.as_millis();
+        if timeout == 0 || timeout > 0xFE {
+            return Err(Error::InvalidTimeout);
+        }
+        if window >= timeout {
+            return Err(Error::InvalidWindow);
+        }
+
+        self.cmpl.write(window as u8);
+        self.cmph.write(timeout as u8);
+
+        let mut ctrl: u8 = 0;
+        ctrl.set_bit(0, true);
+        if let Some(polarity) = config.input {
+            ctrl.set_bit(1, polarity as u8 == 1);
+            ctrl.set_bit(2, true);
+        }
+        ctrl.set_bit(3, config.interrupt);
+        self.ctrl.write(ctrl);
+
+        if config.interrupt {
+            crate::interrupts::enable(crate::board::vectors::WDOG_EWM);
+        }
+        Ok(())
+    }
+
+    /// Route EWM_IN to a pin. Available on pin 0 (PTB16) and pin 6 (PTD4).
+    pub fn set_input_pin(&mut self, pin: impl Into<Pin>) -> Result<(), Error> {
+        let mut pin = pin.into();
+        let mode = match (pin.portname, pin.id) {
+            (PortName::B, 16) => 7,
+            (PortName::D, 4) => 6,
+            _ => return Err(Error::InvalidPin),
+        };
+        unsafe { pin.set_pin_mode(mode) };
+        Ok(())
+    }
+
+    /// Route EWM_OUT to a pin. Available on pin 1 (PTB17) and pin 20 (PTD5).
+    /// EWM_OUT is active low, use a pull-down resistor on the board if the external circuitry
+    /// must stay in its safe state until the EWM is configured.
+    pub fn set_output_pin(&mut self, pin: impl Into<Pin>) -> Result<(), Error> {
+        let mut pin = pin.into();
+        let mode = match (pin.portname, pin.id) {
+            (PortName::B, 17) => 7,
+            (PortName::D, 5) => 6,
+            _ => return Err(Error::InvalidPin),
+        };
+        unsafe { pin.set_pin_mode(mode) };
+        Ok(())
+    }
+
+    /// Refresh the EWM. Must be called between the window and the timeout.
+    pub fn feed(&mut self) {
+        // The two writes must happen in less than 15 bus cycles
+        crate::interrupts::free(|| {
+            self.serv.write(0xB4);
+            self.serv.write(0x2C);
+        });
+    }
+}
+
+/// Register a function to call when EWM_OUT is asserted, see `EwmConfig::interrupt`.
+pub fn set_interrupt_handler(handler: fn()) {
+    crate::interrupts::free(|| unsafe {
+        INTERRUPT_HANDLER = Some(handler);
+    });
+}
+
+/// Called from the vector shared with the watchdog, once we know the watchdog did not fire.
+pub(crate) unsafe fn on_interrupt() {
+    let ewm = Ewm::new();
+    if !ewm.ctrl.read().get_bit(3) {
+        return;
+    }
+    // The only way to deassert the interrupt request is to clear INTEN
+    ewm.ctrl.update(|ctrl| {
+        ctrl.set_bit(3, false);
+    });
+    if let Some(handler) = INTERRUPT_HANDLER {
+        handler();
+    }
+}
@@ -1,3 +1,68 @@
+use volatile::Volatile;
+
+/// Nested Vectored Interrupt Controller, the interrupts are numbered by their position in the
+/// vector table (see `board::vectors`), the NVIC only handle the vectors from 16. The number given
+/// to `isr_ignore` below is the vector on the MK20DX256.
+const NVIC_ISER: u32 = 0xE000_E100;
+const NVIC_ICER: u32 = 0xE000_E180;
+const NVIC_ICPR: u32 = 0xE000_E280;
+const NVIC_IPR: u32 = 0xE000_E400;
+
+unsafe fn nvic_bit(base: u32, vector: usize) -> (&'static mut Volatile<u32>, u32) {
+    assert!(vector >= 16 && vector < crate::board::vectors::COUNT);
+    let irq = vector - 16;
+    let reg = &mut *((base + (irq / 32) as u32 * 4) as *mut Volatile<u32>);
+    (reg, 1 << (irq % 32))
+}
+
+/// Enable an interrupt in the NVIC. Any pending request is discarded first.
+pub fn enable(vector: usize) {
+    unsafe {
+        let (icpr, bit) = nvic_bit(NVIC_ICPR, vector);
+        icpr.write(bit);
+        let (iser, bit) = nvic_bit(NVIC_ISER, vector);
+        iser.write(bit);
+    }
+}
+
+/// Disable an interrupt in the NVIC.
+pub fn disable(vector: usize) {
+    unsafe {
+        let (icer, bit) = nvic_bit(NVIC_ICER, vector);
+        icer.write(bit);
+    }
+}
+
+/// Set the priority of an interrupt, 0 is the highest priority. Only the 4 upper bits are
+/// implemented on the teensy.
+pub fn set_priority(vector: usize, priority: u8) {
+    assert!(vector >= 16 && vector < crate::board::vectors::COUNT);
+    unsafe {
+        let ipr = &mut *((NVIC_IPR + (vector - 16) as u32) as *mut Volatile<u8>);
+        ipr.write(priority);
+    }
+}
+
+/// Execute the closure with all the interrupts masked. The previous state is restored
+/// afterward so the calls can be nested.
+pub fn free<F: FnOnce() -> R, R>(f: F) -> R {
+    let primask: u32;
+    unsafe {
+        asm!("mrs {}, PRIMASK", out(reg) primask);
+        asm!("cpsid i");
+    }
+
+    let r = f();
+
+    // the interrupts were enabled before us
+    if primask & 1 == 0 {
+        unsafe {
+            asm!("cpsie i");
+        }
+    }
+    r
+}
+
 pub unsafe extern "C" fn isr_panic(_level: u8) -> ! {
     core::panic!();
 }
@@ -32,7 +97,7 @@ pub unsafe extern "C" fn isr_pendablesrvreq() {
     isr_ignore(14)
 }
 pub unsafe extern "C" fn isr_systick() {
-    isr_ignore(15)
+    crate::time::on_tick();
 }
 pub unsafe extern "C" fn isr_dma_ch0_complete() {
     isr_ignore(16)
@@ -96,10 +161,15 @@ pub unsafe extern "C" fn isr_low_voltage_warning() {
     isr_ignore(36)
 }
 pub unsafe extern "C" fn isr_low_voltage_wakeup() {
+    #[cfg(not(any(feature = "teensy_35", feature = "teensy_36")))]
+    crate::llwu::on_interrupt();
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
     isr_ignore(37)
 }
 pub unsafe extern "C" fn isr_wdog_or_emw() {
-    isr_ignore(38)
+    if !crate::watchdog::on_interrupt() {
+        crate::ewm::on_interrupt();
+    }
 }
 
 pub unsafe extern "C" fn isr_i2c0() {
@@ -190,22 +260,22 @@ pub unsafe extern "C" fn isr_cmt() {
     isr_ignore(81)
 }
 pub unsafe extern "C" fn isr_rtc_alarm() {
-    isr_ignore(82)
+    crate::rtc::on_alarm();
 }
 pub unsafe extern "C" fn isr_rtc_seconds() {
-    isr_ignore(83)
+    crate::rtc::on_seconds();
 }
 pub unsafe extern "C" fn isr_pit_ch0() {
-    isr_ignore(84)
+    crate::pit::on_interrupt(0);
 }
 pub unsafe extern "C" fn isr_pit_ch1() {
-    isr_ignore(85)
+    crate::pit::on_interrupt(1);
 }
 pub unsafe extern "C" fn isr_pit_ch2() {
-    isr_ignore(86)
+    crate::pit::on_interrupt(2);
 }
 pub unsafe extern "C" fn isr_pit_ch3() {
-    isr_ignore(87)
+    crate::pit::on_interrupt(3);
 }
 pub unsafe extern "C" fn isr_pdb() {
     isr_ignore(88)
@@ -222,29 +292,32 @@ pub unsafe extern "C" fn isr_dac0() {
 }
 
 pub unsafe extern "C" fn isr_tsi() {
+    #[cfg(not(any(feature = "teensy_35", feature = "teensy_36")))]
+    crate::tsi::on_interrupt();
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
     isr_ignore(99)
 }
 pub unsafe extern "C" fn isr_mcg() {
     isr_ignore(100)
 }
 pub unsafe extern "C" fn isr_lpt() {
-    isr_ignore(101)
+    crate::lptmr::on_interrupt();
 }
 
 pub unsafe extern "C" fn isr_port_a() {
-    isr_ignore(103)
+    crate::port::on_interrupt(crate::port::PortName::A);
 }
 pub unsafe extern "C" fn isr_port_b() {
-    isr_ignore(104)
+    crate::port::on_interrupt(crate::port::PortName::B);
 }
 pub unsafe extern "C" fn isr_port_c() {
-    isr_ignore(105)
+    crate::port::on_interrupt(crate::port::PortName::C);
 }
 pub unsafe extern "C" fn isr_port_d() {
-    isr_ignore(106)
+    crate::port::on_interrupt(crate::port::PortName::D);
 }
 pub unsafe extern "C" fn isr_port_e() {
-    isr_ignore(107)
+    crate::port::on_interrupt(crate::port::PortName::E);
 }
 
 pub unsafe extern "C" fn isr_software() {
@@ -7,7 +7,8 @@
 
 //! # Teensy
 //! This crate provide multiple helpers functions to manipulate the teensy in baremetal rust.
-//! Currently everything is tested on a teensy 3.2. It provide:
+//! Currently everything is tested on a teensy 3.2, the other boards are selected with a feature
+//! (see the `board` module). It provide:
 //! * A bootloader
 //! * Helper to handle the gpio pins
 //!
@@ -59,11 +60,7 @@
 //! To compile code for the teensy this crate provide multiple section that you'll need to put in
 //! the right place. To do this we provide a linker script that you can copy paste as `layout.ld`:
 //! ```ld
-//! MEMORY
-//! {
-//! 	FLASH (rx) : ORIGIN = 0x00000000, LENGTH = 256K
-//! 	RAM  (rwx) : ORIGIN = 0x1FFF8000, LENGTH = 64K
-//! }
+//! INCLUDE memory.x
 //! EXTERN(_INTERRUPTS);
 //! SECTIONS
 //! {
@@ -82,6 +79,29 @@
 //! 	    *(.rodata .rodata.*);
 //! 	    . = ALIGN(4);
 //!     } > FLASH
+//!     .data : ALIGN(4){
+//! 	    _sdata = .;
+//! 	    *(.data .data.*);
+//! 	    . = ALIGN(4);
+//! 	    _edata = .;
+//!     } > RAM AT > FLASH
+//!     _sidata = LOADADDR(.data);
+//!     .bss (NOLOAD) : ALIGN(4){
+//! 	    _sbss = .;
+//! 	    *(.bss .bss.*);
+//! 	    . = ALIGN(4);
+//! 	    _ebss = .;
+//!     } > RAM
+//!     .noinit (NOLOAD) : ALIGN(4){
+//! 	    *(.noinit .noinit.*);
+//! 	    . = ALIGN(4);
+//!     } > RAM
+//!     .bitband 0x20000000 (NOLOAD) : ALIGN(4){
+//! 	    _sbitband = .;
+//! 	    *(.bitband .bitband.*);
+//! 	    . = ALIGN(4);
+//! 	    _ebitband = .;
+//!     } > RAM
 //!     /DISCARD/ : {
 //! 	    *(.ARM.*)
 //!     }
@@ -91,6 +111,8 @@
 //! **This script is also available with comments in the repository as
 //! [`layout.ld`](https://github.com/irevoire/teensy/blob/master/layout.ld).**
 //!
+//! The `memory.x` file holding the flash and RAM regions of your board is provided by the crate.
+//!
 //! ### Target
 //! In order to compile your code for the teensy you need to specify the architecture you are
 //! targetting. Create a `.cargo/config` file like that:
@@ -121,7 +143,8 @@
 //!
 //! ## Flashing the Teensy
 //! We provide a Makefile to generate a binary for the teensy 3.2 and sending it using the
-//! `teensy_loader_cli` command:
+//! `teensy_loader_cli` command (use `-mmcu=mk20dx128`, `mk64fx512` or `mk66fx1m0` for the
+//! teensy 3.0, 3.5 and 3.6):
 //! ```Makefile
 //! BIN=my_application
 //! OUTDIR=target/thumbv7em-none-eabi/release
@@ -164,26 +187,52 @@
 //! ](https://github.com/irevoire/teensy_blink_manual)
 //!
 
+/// Atomic single bit accesses to the registers and the SRAM.
+pub mod bitband;
+/// The memory map, pins and peripherals of the selected teensy.
+pub mod board;
 /// This module provide all the needed functions to boot the teensy.
 pub mod boot;
+/// The DWT cycle counter, to measure time precisely.
+pub mod cycles;
+/// The External Watchdog Monitor.
+pub mod ewm;
 pub mod interrupts;
+/// The Low-Leakage Wakeup Unit, of the teensy 3.0, 3.1 and 3.2.
+#[cfg(not(any(feature = "teensy_35", feature = "teensy_36")))]
+pub mod llwu;
+/// The Low-Power Timer.
+pub mod lptmr;
 /// The Multipurpose Clock Generator.
 pub mod mcg;
 /// The Oscillator Unit.
 pub mod osc;
 /// Helper module to define easily panic function.
 pub mod panic;
+/// The Periodic Interrupt Timer.
+pub mod pit;
 /// The port, pins and gpio.
 pub mod port;
+/// The Real Time Clock.
+pub mod rtc;
 /// The System Integration Module.
 pub mod sim;
+/// The System Mode Controller, to enter the low power modes.
+pub mod smc;
 /// The watchdog is a piece of hardware which will reset the microcontroller unless the running
 /// application “checks in” in a certain interval.
 pub mod watchdog;
 
 // I think the next modules should be moved to a kindof "common" module
-/// Implementation of the sleep function
+/// Implementation of the sleep function and of the embedded-hal delays
 pub mod sleep;
+/// A monotonic clock based on the SysTick
+pub mod time;
+/// One-shot and periodic software timers, driven by the SysTick
+pub mod timers;
+/// The Touch Sensing Input, of the teensy 3.0, 3.1 and 3.2.
+#[cfg(not(any(feature = "teensy_35", feature = "teensy_36")))]
+pub mod tsi;
 pub mod uart;
 
 /// The only function you will need to implements
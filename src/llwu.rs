@@ -0,0 +1,184 @@
+//! # Low-Leakage Wakeup Unit (LLWU)
+//! In the LLS and VLLS modes (see the `smc` module) the interrupts can't wake the chip up, only
+//! the LLWU can. It watches up to 16 pins and 8 internal modules.
+//!
+//! On the teensy 3.0, 3.1 and 3.2 the wakeup pins are 2, 4, 6, 7, 9, 10, 11, 13, 16, 21, 22, 26,
+//! 30 and 33. The LLWU of the MK66 has more pins and another register map, this module is not
+//! available on the teensy 3.5 and 3.6.
+//! ```rust
+//! let llwu = unsafe { llwu::Llwu::new() };
+//! llwu.enable_pin(&pin, llwu::Edge::Falling).unwrap();
+//! llwu.enable_module(llwu::WakeupModule::Lptmr);
+//! smc.stop(smc::StopMode::Lls).unwrap();
+//! match llwu.wakeup_source() {
+//!     Some(llwu::WakeupSource::Pin(n)) => (),
+//!     Some(llwu::WakeupSource::Module(m)) => (),
+//!     None => (),
+//! }
+//! ```
+
+use crate::port::{Pin, PortName};
+use bit_field::BitField;
+use volatile::Volatile;
+
+const LLWU_VECTOR: usize = crate::board::vectors::LLWU;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// This pin is not connected to the LLWU
+    InvalidPin,
+}
+
+#[derive(Clone, Copy)]
+pub enum Edge {
+    Rising = 1,
+    Falling = 2,
+    Both = 3,
+}
+
+/// The internal modules which can wake the chip up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeupModule {
+    Lptmr = 0,
+    Cmp0 = 1,
+    Cmp1 = 2,
+    Cmp2 = 3,
+    Tsi = 4,
+    RtcAlarm = 5,
+    RtcSeconds = 7,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeupSource {
+    /// The wakeup pin `LLWU_Pn`, see `wakeup_pin`
+    Pin(u8),
+    Module(WakeupModule),
+}
+
+/// The `LLWU_Pn` number of a pin.
+pub fn wakeup_pin(pin: &Pin) -> Result<u8, Error> {
+    match (pin.portname, pin.id) {
+        (PortName::E, 1) => Ok(0),
+        (PortName::E, 2) => Ok(1),
+        (PortName::E, 4) => Ok(2),
+        (PortName::A, 4) => Ok(3),
+        (PortName::A, 13) => Ok(4),
+        (PortName::B, 0) => Ok(5),
+        (PortName::C, 1) => Ok(6),
+        (PortName::C, 3) => Ok(7),
+        (PortName::C, 4) => Ok(8),
+        (PortName::C, 5) => Ok(9),
+        (PortName::C, 6) => Ok(10),
+        (PortName::C, 11) => Ok(11),
+        (PortName::D, 0) => Ok(12),
+        (PortName::D, 2) => Ok(13),
+        (PortName::D, 4) => Ok(14),
+        (PortName::D, 6) => Ok(15),
+        _ => Err(Error::InvalidPin),
+    }
+}
+
+static mut HANDLER: Option<fn(WakeupSource)> = None;
+
+#[repr(C, packed)]
+pub struct Llwu {
+    pe: [Volatile<u8>; 4],
+    me: Volatile<u8>,
+    f1: Volatile<u8>,
+    f2: Volatile<u8>,
+    f3: Volatile<u8>,
+    filt1: Volatile<u8>,
+    filt2: Volatile<u8>,
+    rst: Volatile<u8>,
+}
+
+impl Llwu {
+    pub unsafe fn new() -> &'static mut Llwu {
+        &mut *(0x4007_C000 as *mut Llwu)
+    }
+
+    /// Wake the chip up on an edge of the pin. The pin must be configured as a digital input.
+    pub fn enable_pin(&mut self, pin: &Pin, edge: Edge) -> Result<(), Error> {
+        self.set_pin(wakeup_pin(pin)?, edge as u8);
+        Ok(())
+    }
+
+    pub fn disable_pin(&mut self, pin: &Pin) -> Result<(), Error> {
+        self.set_pin(wakeup_pin(pin)?, 0);
+        Ok(())
+    }
+
+    fn set_pin(&mut self, n: u8, mode: u8) {
+        let (reg, bit) = ((n / 4) as usize, (n % 4) as usize * 2);
+        self.pe[reg].update(|pe| {
+            pe.set_bits(bit..bit + 2, mode);
+        });
+    }
+
+    pub fn enable_module(&mut self, module: WakeupModule) {
+        self.me.update(|me| {
+            me.set_bit(module as usize, true);
+        });
+    }
+
+    pub fn disable_module(&mut self, module: WakeupModule) {
+        self.me.update(|me| {
+            me.set_bit(module as usize, false);
+        });
+    }
+
+    /// What woke the chip up. Still valid after a wakeup from VLLS, until the flags are
+    /// cleared.
+    pub fn wakeup_source(&self) -> Option<WakeupSource> {
+        let pins = self.f1.read() as u16 | (self.f2.read() as u16) << 8;
+        if pins != 0 {
+            return Some(WakeupSource::Pin(pins.trailing_zeros() as u8));
+        }
+
+        let modules = self.f3.read();
+        [
+            WakeupModule::Lptmr,
+            WakeupModule::Cmp0,
+            WakeupModule::Cmp1,
+            WakeupModule::Cmp2,
+            WakeupModule::Tsi,
+            WakeupModule::RtcAlarm,
+            WakeupModule::RtcSeconds,
+        ]
+        .iter()
+        .find(|module| modules.get_bit(**module as usize))
+        .map(|module| WakeupSource::Module(*module))
+    }
+
+    /// Clear the pin flags. The module flags are cleared by clearing the flag of the module
+    /// itself, for example `lptmr::Lptmr::clear_flag`.
+    pub fn clear_pin_flags(&mut self) {
+        // write one to clear
+        self.f1.write(0xFF);
+        self.f2.write(0xFF);
+    }
+}
+
+/// Register a function called with the wakeup source when the chip wakes up from LLS.
+/// The pin flags and the LPTMR flag are cleared for you, the other modules flags must be
+/// cleared by the handler.
+pub fn set_handler(handler: fn(WakeupSource)) {
+    crate::interrupts::free(|| unsafe {
+        HANDLER = Some(handler);
+    });
+    crate::interrupts::enable(LLWU_VECTOR);
+}
+
+/// Called from the LLWU interrupt vector.
+pub(crate) unsafe fn on_interrupt() {
+    let llwu = Llwu::new();
+    let source = llwu.wakeup_source();
+    match source {
+        Some(WakeupSource::Pin(_)) => llwu.clear_pin_flags(),
+        Some(WakeupSource::Module(WakeupModule::Lptmr)) => crate::lptmr::Lptmr::new().clear_flag(),
+        _ => (),
+    }
+    if let (Some(handler), Some(source)) = (HANDLER, source) {
+        handler(source);
+    }
+}
@@ -0,0 +1,233 @@
+//! # Low-Power Timer (LPTMR)
+//! A 16 bits counter which keeps running in all the low power modes, and can wake the chip up
+//! through the LLWU (it is the wakeup module 0). It works in two modes:
+//! - time counter: counts the cycles of one of the clocks, optionally divided by a prescaler
+//! - pulse counter: counts the edges of an input, optionally filtered by a glitch filter
+//!
+//! When the counter matches the compare value the flag is set, an interrupt can be triggered,
+//! and the counter is reset (unless the free running mode is enabled).
+//!
+//! Counting the pulses of the pin 13 while sleeping:
+//! ```rust
+//! let (sim, lptmr) = unsafe { (sim::Sim::new(), lptmr::Lptmr::new()) };
+//! sim.enable_clock(sim::Clock::Lptmr);
+//! let input = lptmr.set_input_pin(pins.p13).unwrap();
+//! lptmr
+//!     .pulse_counter(input, lptmr::Polarity::ActiveHigh, lptmr::ClockSource::Lpo, Some(2), 100)
+//!     .unwrap();
+//! lptmr.start();
+//! ```
+
+use crate::port::{Pin, PortName};
+use bit_field::BitField;
+use volatile::Volatile;
+
+const LPTMR_VECTOR: usize = crate::board::vectors::LPTMR;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The prescaler divides by `2^(n + 1)` with n between 0 and 15, and the glitch filter
+    /// waits for `2^n` clock edges with n between 1 and 15
+    InvalidPrescaler(u8),
+    /// This pin can't be used as a pulse input
+    InvalidPin,
+}
+
+/// The clock used by the prescaler and the glitch filter.
+#[derive(Clone, Copy)]
+pub enum ClockSource {
+    /// The MCG internal reference clock, slow or fast IRC
+    McgIrclk = 0,
+    /// The 1kHz low power oscillator, running in all modes
+    Lpo = 1,
+    /// The 32kHz clock selected by `sim::Sim::set_erclk32k_source`
+    Erclk32k = 2,
+    /// The main crystal oscillator, see `osc::Osc::enable_in_stop`
+    Oscerclk = 3,
+}
+
+/// The input counted in pulse counter mode.
+#[derive(Clone, Copy)]
+pub enum PulseInput {
+    /// The output of the comparator 0
+    Cmp0 = 0,
+    /// LPTMR_ALT1, PTA19 which is wired to the crystal on the teensy
+    Alt1 = 1,
+    /// LPTMR_ALT2, on the pin 13 (PTC5)
+    Alt2 = 2,
+}
+
+#[derive(Clone, Copy)]
+pub enum Polarity {
+    /// Count the rising edges
+    ActiveHigh = 0,
+    /// Count the falling edges
+    ActiveLow = 1,
+}
+
+static mut HANDLER: Option<fn()> = None;
+
+#[repr(C, packed)]
+pub struct Lptmr {
+    csr: Volatile<u32>,
+    psr: Volatile<u32>,
+    cmr: Volatile<u32>,
+    cnr: Volatile<u32>,
+}
+
+impl Lptmr {
+    /// The LPTMR clock gate (`sim::Clock::Lptmr`) must be enabled before using it.
+    pub unsafe fn new() -> &'static mut Lptmr {
+        &mut *(0x4004_0000 as *mut Lptmr)
+    }
+
+    /// Count the cycles of `clock`. The counter is incremented every `2^(prescaler + 1)`
+    /// cycles, or every cycle if there is no prescaler. The timer is stopped.
+    pub fn time_counter(
+        &mut self,
+        clock: ClockSource,
+        prescaler: Option<u8>,
+        compare: u16,
+    ) -> Result<(), Error> {
+        if let Some(prescaler) = prescaler {
+            if prescaler > 15 {
+                return Err(Error::InvalidPrescaler(prescaler));
+            }
+        }
+
+        self.stop();
+        self.set_prescaler(clock, prescaler);
+        self.csr.update(|csr| {
+            csr.set_bit(1, false);
+        });
+        self.set_compare(compare);
+        Ok(())
+    }
+
+    /// Count the pulses of `input`. With a glitch filter, a change of the input is only
+    /// recognized after `2^glitch_filter` edges of `clock`. The timer is stopped.
+    pub fn pulse_counter(
+        &mut self,
+        input: PulseInput,
+        polarity: Polarity,
+        clock: ClockSource,
+        glitch_filter: Option<u8>,
+        compare: u16,
+    ) -> Result<(), Error> {
+        if let Some(filter) = glitch_filter {
+            if filter == 0 || filter > 15 {
+                return Err(Error::InvalidPrescaler(filter));
+            }
+        }
+
+        self.stop();
+        self.set_prescaler(clock, glitch_filter);
+        self.csr.update(|csr| {
+            csr.set_bit(1, true);
+            csr.set_bit(3, polarity as u8 == 1);
+            csr.set_bits(4..6, input as u32);
+        });
+        self.set_compare(compare);
+        Ok(())
+    }
+
+    fn set_prescaler(&mut self, clock: ClockSource, prescaler: Option<u8>) {
+        let mut psr: u32 = 0;
+        psr.set_bits(0..2, clock as u32);
+        match prescaler {
+            Some(prescaler) => psr.set_bits(3..7, prescaler as u32),
+            None => psr.set_bit(2, true),
+        };
+        self.psr.write(psr);
+    }
+
+    /// Mux a pin to the pulse counter and return the input to use in `pulse_counter`.
+    /// Only the pin 13 (PTC5) is available on the teensy.
+    pub fn set_input_pin(&mut self, pin: impl Into<Pin>) -> Result<PulseInput, Error> {
+        let mut pin = pin.into();
+        match (pin.portname, pin.id) {
+            (PortName::C, 5) => {
+                unsafe { pin.set_pin_mode(3) };
+                Ok(PulseInput::Alt2)
+            }
+            _ => Err(Error::InvalidPin),
+        }
+    }
+
+    /// The flag is set when the counter reaches this value. Can only be changed while the timer
+    /// is stopped or when the flag is set.
+    pub fn set_compare(&mut self, compare: u16) {
+        self.cmr.write(compare as u32);
+    }
+
+    /// TFC: don't reset the counter when it reaches the compare value, let it overflow.
+    pub fn set_free_running(&mut self, free_running: bool) {
+        self.csr.update(|csr| {
+            csr.set_bit(2, free_running);
+        });
+    }
+
+    pub fn start(&mut self) {
+        self.csr.update(|csr| {
+            csr.set_bit(0, true);
+        });
+    }
+
+    /// Stop the timer, the counter is reset.
+    pub fn stop(&mut self) {
+        self.csr.update(|csr| {
+            csr.set_bit(0, false);
+        });
+    }
+
+    /// The current value of the counter.
+    pub fn count(&mut self) -> u16 {
+        // writing any value latches the counter
+        self.cnr.write(0);
+        self.cnr.read() as u16
+    }
+
+    /// TCF: the counter reached the compare value.
+    pub fn has_expired(&self) -> bool {
+        self.csr.read().get_bit(7)
+    }
+
+    pub fn clear_flag(&mut self) {
+        // write one to clear
+        self.csr.update(|csr| {
+            csr.set_bit(7, true);
+        });
+    }
+
+    /// Interrupt when the counter reaches the compare value, the handler registered with
+    /// `set_handler` is called.
+    pub fn enable_interrupt(&mut self) {
+        self.csr.update(|csr| {
+            csr.set_bit(6, true);
+        });
+        crate::interrupts::enable(LPTMR_VECTOR);
+    }
+
+    pub fn disable_interrupt(&mut self) {
+        self.csr.update(|csr| {
+            csr.set_bit(6, false);
+        });
+        crate::interrupts::disable(LPTMR_VECTOR);
+    }
+}
+
+/// Register the function called when the counter reaches the compare value. The flag is cleared
+/// for you.
+pub fn set_handler(handler: fn()) {
+    crate::interrupts::free(|| unsafe {
+        HANDLER = Some(handler);
+    });
+}
+
+/// Called from the LPTMR interrupt vector.
+pub(crate) unsafe fn on_interrupt() {
+    Lptmr::new().clear_flag();
+    if let Some(handler) = HANDLER {
+        handler();
+    }
+}
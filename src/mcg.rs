@@ -3,8 +3,10 @@ use core::mem;
 use bit_field::BitField;
 use volatile::Volatile;
 
-/// TODO This value should not be hardcoded and should change depending on the choosen frequency
-pub const F_CPU: u32 = 72_000_000;
+/// The frequency of the core set by `boot::init`, it depends on the board.
+pub const F_CPU: u32 = crate::board::F_CPU;
+/// The frequency of the bus clock, used by most of the peripherals.
+pub const F_BUS: u32 = F_CPU / crate::board::CLOCK_DIVIDERS.1;
 
 #[repr(C, packed)]
 pub struct Mcg {
@@ -28,6 +30,189 @@ impl Mcg {
     pub unsafe fn new() -> &'static mut Mcg {
         &mut *(0x4006_4000 as *mut Mcg)
     }
+
+    /// OSCINIT0: the crystal oscillator has completed its initialization cycles.
+    pub fn osc_ready(&self) -> bool {
+        self.s.read().get_bit(1)
+    }
+
+    /// OSCSEL: choose which oscillator is used as the external reference clock of the MCG.
+    /// Only change it while the MCG is not using the external reference.
+    pub fn select_external_oscillator(&mut self, osc: ExternalOscillator) {
+        self.c7.update(|c7| {
+            c7.set_bit(0, osc as u8 == 1);
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The fast IRC trim (FCTRIM) only has 4 bits.
+    InvalidTrim(u8),
+    /// The automatic trim machine needs a bus clock between 8MHz and 16MHz.
+    InvalidAutoTrimClock(u32),
+    /// The compare value of the automatic trim machine does not fit in 16 bits.
+    InvalidAutoTrimTarget(u32),
+    /// ATMF: the automatic trim machine aborted.
+    AutoTrimFailed,
+}
+
+/// The internal reference clocks.
+#[derive(Clone, Copy)]
+pub enum Irc {
+    /// The slow internal reference clock, nominally 32.768kHz
+    Slow = 0,
+    /// The fast internal reference clock, nominally 4MHz
+    Fast = 1,
+}
+
+/// Trim values of the internal reference clocks.
+/// The factory values are loaded by the hardware at reset, so reading them with `Mcg::trim`
+/// before modifying anything gives you the factory trim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IrcTrim {
+    /// SCTRIM: the slow IRC trim, a higher value means a slower clock
+    pub slow: u8,
+    /// SCFTRIM: the slow IRC fine trim, a half step of `slow`
+    pub slow_fine: bool,
+    /// FCTRIM: the fast IRC trim, between 0 and 15
+    pub fast: u8,
+}
+
+/// DRST_DRS: the DCO range, multiplying the FLL reference clock.
+#[derive(Clone, Copy)]
+pub enum FllRange {
+    /// 640 times the reference, or 732 with DMX32 (24MHz from 32.768kHz)
+    Low = 0,
+    /// 1280 times the reference, or 1464 with DMX32 (48MHz from 32.768kHz)
+    Mid = 1,
+    /// 1920 times the reference, or 2197 with DMX32 (72MHz from 32.768kHz)
+    MidHigh = 2,
+    /// 2560 times the reference, or 2929 with DMX32 (96MHz from 32.768kHz)
+    High = 3,
+}
+
+impl Mcg {
+    /// Read the current trim of the internal reference clocks.
+    pub fn trim(&self) -> IrcTrim {
+        let c4 = self.c4.read();
+        IrcTrim {
+            slow: self.c3.read(),
+            slow_fine: c4.get_bit(0),
+            fast: c4.get_bits(1..5),
+        }
+    }
+
+    /// Apply a user trim on the internal reference clocks.
+    pub fn set_trim(&mut self, trim: IrcTrim) -> Result<(), Error> {
+        if trim.fast > 0b1111 {
+            return Err(Error::InvalidTrim(trim.fast));
+        }
+
+        self.c3.write(trim.slow);
+        self.c4.update(|c4| {
+            c4.set_bit(0, trim.slow_fine);
+            c4.set_bits(1..5, trim.fast);
+        });
+        Ok(())
+    }
+
+    /// Load the trim stored in flash by the programming tools, following the Freescale
+    /// convention: SCTRIM at 0x3FF and SCFTRIM / FCTRIM in the low bits of 0x3FE.
+    /// Return false and keep the current trim if nothing was programmed there.
+    pub fn load_flash_trim(&mut self) -> bool {
+        let (sctrim, c4) = unsafe {
+            (
+                core::ptr::read_volatile(0x3FF as *const u8),
+                core::ptr::read_volatile(0x3FE as *const u8),
+            )
+        };
+        if sctrim == 0xFF {
+            return false;
+        }
+
+        self.c3.write(sctrim);
+        self.c4.update(|c4_reg| {
+            c4_reg.set_bits(0..5, c4.get_bits(0..5));
+        });
+        true
+    }
+
+    /// Run the automatic trim machine on one of the internal reference clocks, and return the
+    /// trim it found.
+    ///
+    /// The ATM measures the IRC against the bus clock, which must come from an accurate source
+    /// (the crystal) and run between 8MHz and 16MHz. `target` is the wanted IRC frequency in Hz.
+    /// The trimmed IRC must not be the one driving the bus clock while the ATM is running.
+    pub fn auto_trim(&mut self, irc: Irc, bus_clock: u32, target: u32) -> Result<IrcTrim, Error> {
+        if bus_clock < 8_000_000 || bus_clock > 16_000_000 {
+            return Err(Error::InvalidAutoTrimClock(bus_clock));
+        }
+
+        // ATCV = 21 * (Fbus / Firc)
+        let atcv = 21 * bus_clock as u64 / target as u64;
+        if atcv > 0xFFFF {
+            return Err(Error::InvalidAutoTrimTarget(target));
+        }
+        self.atcvh.write((atcv >> 8) as u8);
+        self.atcvl.write(atcv as u8);
+
+        self.update_sc(|sc| {
+            sc.set_bit(6, irc as u8 == 1); // ATMS
+            sc.set_bit(5, true); // clear ATMF
+        });
+        self.update_sc(|sc| {
+            sc.set_bit(7, true); // ATME
+        });
+
+        // The hardware clear ATME once the trim is done
+        while self.sc.read().get_bit(7) {}
+
+        if self.sc.read().get_bit(5) {
+            self.update_sc(|sc| {
+                sc.set_bit(5, true);
+            });
+            return Err(Error::AutoTrimFailed);
+        }
+        Ok(self.trim())
+    }
+
+    /// Select the DCO range of the FLL. With DMX32 the DCO is fine tuned for a 32.768kHz
+    /// reference.
+    /// The FLL needs about 1ms to settle at its new frequency.
+    pub fn set_fll_range(&mut self, range: FllRange, dmx32: bool) {
+        self.c4.update(|c4| {
+            c4.set_bits(5..7, range as u8);
+            c4.set_bit(7, dmx32);
+        });
+    }
+
+    /// The current multiplication factor of the FLL.
+    pub fn fll_factor(&self) -> u32 {
+        let c4 = self.c4.read();
+        let range = c4.get_bits(5..7) as usize;
+        if c4.get_bit(7) {
+            [732, 1464, 2197, 2929][range]
+        } else {
+            [640, 1280, 1920, 2560][range]
+        }
+    }
+
+    /// LOCS0 and ATMF are cleared by writing 1, they must not be written back.
+    fn update_sc<F: FnOnce(&mut u8)>(&mut self, f: F) {
+        let mut sc = self.sc.read();
+        sc.set_bit(0, false);
+        sc.set_bit(5, false);
+        f(&mut sc);
+        self.sc.write(sc);
+    }
+}
+
+pub enum ExternalOscillator {
+    /// The main 16MHz crystal oscillator
+    System = 0,
+    /// The 32.768kHz oscillator of the RTC, see `osc::RtcOsc`
+    Rtc32k = 1,
 }
 
 pub enum OscRange {
@@ -47,6 +232,16 @@ pub struct Fei {
 }
 
 impl Fei {
+    /// Select the DCO range of the FLL, see `Mcg::set_fll_range`.
+    pub fn set_fll_range(&mut self, range: FllRange, dmx32: bool) {
+        self.mcg.set_fll_range(range, dmx32);
+    }
+
+    /// The FLL output frequency for a slow IRC trimmed to `irc` Hz.
+    pub fn frequency(&self, irc: u32) -> u32 {
+        irc * self.mcg.fll_factor()
+    }
+
     pub fn enable_xtal(&mut self, range: OscRange) {
         self.mcg.c2.update(|c2| {
             c2.set_bits(4..6, range as u8);
@@ -54,7 +249,7 @@ impl Fei {
         });
 
         // Wait for the crystal oscillator to become enabled.
-        while !self.mcg.s.read().get_bit(1) {}
+        while !self.mcg.osc_ready() {}
     }
 
     pub fn use_external(self, divide: u32) -> Fbe {
@@ -107,12 +302,19 @@ pub struct Fbe {
 }
 
 impl Fbe {
+    /// The PLL runs at `crystal / denominator * numerator`, halved on the teensy 3.6.
     pub fn enable_pll(self, numerator: u8, denominator: u8) -> Pbe {
-        if numerator < 24 || numerator > 55 {
+        // The K66 of the teensy 3.6 has a different PLL, with a VCO twice as fast
+        #[cfg(not(feature = "teensy_36"))]
+        let (numerators, denominators) = (24..=55, 1..=25);
+        #[cfg(feature = "teensy_36")]
+        let (numerators, denominators) = (16..=47, 1..=8);
+
+        if !numerators.contains(&numerator) {
             panic!("Invalid PLL VCO divide factor: {}", numerator);
         }
 
-        if denominator < 1 || denominator > 25 {
+        if !denominators.contains(&denominator) {
             panic!("Invalid PLL reference divide factor: {}", denominator);
         }
 
@@ -121,7 +323,7 @@ impl Fbe {
         });
 
         self.mcg.c6.update(|c6| {
-            c6.set_bits(0..5, numerator - 24);
+            c6.set_bits(0..5, numerator - numerators.start());
             c6.set_bit(6, true);
         });
 
@@ -1,6 +1,36 @@
+//! # Oscillators
+//! The teensy has two crystal oscillators:
+//! - The main system oscillator (OSC), driven by the 16MHz crystal. It is started by the MCG
+//!   (see `mcg::Fei::enable_xtal`) and this module only configures its load capacitance and the
+//!   OSCERCLK output.
+//! - The 32.768kHz oscillator living in the RTC module. The Teensy 3.2 has pads for the crystal
+//!   but it is not populated by default.
+
 use bit_field::BitField;
 use volatile::Volatile;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The load capacitance must be an even number of picofarads between 0 and 30.
+    InvalidCapacitance(u8),
+}
+
+/// Translate a capacitance in picofarads into the `[SC2P, SC4P, SC8P, SC16P]` bits.
+/// The capacitance control bits are backwards, and start at 2pf.
+fn capacitance_bits(capacitance: u8) -> Result<[bool; 4], Error> {
+    if capacitance % 2 == 1 || capacitance > 30 {
+        return Err(Error::InvalidCapacitance(capacitance));
+    }
+
+    Ok([
+        capacitance.get_bit(1),
+        capacitance.get_bit(2),
+        capacitance.get_bit(3),
+        capacitance.get_bit(4),
+    ])
+}
+
+#[repr(C, packed)]
 pub struct Osc {
     cr: Volatile<u8>,
 }
@@ -10,23 +40,102 @@ impl Osc {
         &mut *(0x4006_5000 as *mut Osc)
     }
 
-    pub fn enable(&mut self, capacitance: u8) {
-        if capacitance % 2 == 1 || capacitance > 30 {
-            panic!("Invalid crystal capacitance value: {}", capacitance)
-        }
+    /// Set the crystal load capacitance and enable the external reference clock (OSCERCLK).
+    pub fn enable(&mut self, capacitance: u8) -> Result<(), Error> {
+        self.set_capacitance(capacitance)?;
+        self.enable_external_reference(true);
+        Ok(())
+    }
+
+    /// Set the crystal load capacitance, in picofarads.
+    pub fn set_capacitance(&mut self, capacitance: u8) -> Result<(), Error> {
+        let [sc2p, sc4p, sc8p, sc16p] = capacitance_bits(capacitance)?;
+
+        self.cr.update(|cr| {
+            cr.set_bit(3, sc2p);
+            cr.set_bit(2, sc4p);
+            cr.set_bit(1, sc8p);
+            cr.set_bit(0, sc16p);
+        });
+        Ok(())
+    }
+
+    /// ERCLKEN: make the oscillator output available to the peripherals as OSCERCLK.
+    pub fn enable_external_reference(&mut self, enable: bool) {
+        self.cr.update(|cr| {
+            cr.set_bit(7, enable);
+        });
+    }
+
+    /// EREFSTEN: keep OSCERCLK running while the chip is in STOP mode.
+    /// Has no effect unless the external reference is enabled.
+    pub fn enable_in_stop(&mut self, enable: bool) {
+        self.cr.update(|cr| {
+            cr.set_bit(5, enable);
+        });
+    }
+
+    /// Return true once the crystal oscillator has completed its startup cycles and is stable
+    /// (OSCINIT0 in the MCG status register).
+    pub fn is_ready(&self, mcg: &crate::mcg::Mcg) -> bool {
+        mcg.osc_ready()
+    }
+
+    /// Block until the crystal oscillator is stable.
+    pub fn wait_ready(&self, mcg: &crate::mcg::Mcg) {
+        while !self.is_ready(mcg) {}
+    }
+}
+
+/// The control register of the RTC module, which holds the 32.768kHz oscillator configuration.
+/// The RTC clock gate (`sim::Clock::Rtc`) must be enabled before using it, otherwise any access
+/// will trigger a bus fault.
+#[repr(C, packed)]
+pub struct RtcOsc {
+    cr: Volatile<u32>,
+}
+
+impl RtcOsc {
+    pub unsafe fn new() -> &'static mut RtcOsc {
+        &mut *(0x4003_D010 as *mut RtcOsc)
+    }
 
-        let mut cr: u8 = 0;
+    /// Set the crystal load capacitance, enable the oscillator and its output to the
+    /// peripherals (ERCLK32K).
+    /// The oscillator needs around a second to stabilize before it can be used, see
+    /// `crate::sleep::sleep_ms`.
+    pub fn enable(&mut self, capacitance: u8) -> Result<(), Error> {
+        self.set_capacitance(capacitance)?;
+        self.cr.update(|cr| {
+            cr.set_bit(8, true); // OSCE
+            cr.set_bit(9, false); // CLKO is active low
+        });
+        Ok(())
+    }
 
-        // The capacitance control bits are backwards, and start at 2pf
-        // We swizzle them all here
-        cr.set_bit(3, capacitance.get_bit(1));
-        cr.set_bit(2, capacitance.get_bit(2));
-        cr.set_bit(1, capacitance.get_bit(3));
-        cr.set_bit(0, capacitance.get_bit(4));
+    /// Stop the 32.768kHz oscillator. The RTC time counter will stop too.
+    pub fn disable(&mut self) {
+        self.cr.update(|cr| {
+            cr.set_bit(9, true);
+            cr.set_bit(8, false);
+        });
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cr.read().get_bit(8)
+    }
 
-        // enable the crystal oscillator
-        cr.set_bit(7, true);
+    /// Set the crystal load capacitance, in picofarads.
+    /// The capacitance should only be changed while the oscillator is disabled.
+    pub fn set_capacitance(&mut self, capacitance: u8) -> Result<(), Error> {
+        let [sc2p, sc4p, sc8p, sc16p] = capacitance_bits(capacitance)?;
 
-        self.cr.write(cr);
+        self.cr.update(|cr| {
+            cr.set_bit(13, sc2p);
+            cr.set_bit(12, sc4p);
+            cr.set_bit(11, sc8p);
+            cr.set_bit(10, sc16p);
+        });
+        Ok(())
     }
 }
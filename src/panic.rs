@@ -50,8 +50,7 @@ macro_rules! blink_panic {
         #[panic_handler]
         fn blink_panic(_pi: &core::panic::PanicInfo) -> ! {
             use embedded_hal::prelude::*;
-            // we don't know how was the crate included
-            use teensy::*;
+            use $crate::*;
             // here we don't know if the port holding the led is on
             // so we need to reconfigure everything
             let (wdog, sim) = unsafe { (watchdog::Watchdog::new(), sim::Sim::new()) };
@@ -75,9 +74,8 @@ macro_rules! uart_panic {
     () => {
         #[panic_handler]
         fn uart_panic(pi: &core::panic::PanicInfo) -> ! {
-            // we don't know how was the crate included
             use core::fmt::Write;
-            use teensy::*;
+            use $crate::*;
             // here we don't know if the port holding the led is on
             // so we need to reconfigure everything
             let (wdog, sim) = unsafe { (watchdog::Watchdog::new(), sim::Sim::new()) };
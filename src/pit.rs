@@ -0,0 +1,224 @@
+//! # Periodic Interrupt Timer (PIT)
+//! The PIT has four 32 bits down counters clocked by the bus clock. When a counter reaches zero
+//! it sets its flag, can trigger an interrupt, and reloads its load value.
+//! ```rust
+//! let (sim, pit) = unsafe { (sim::Sim::new(), pit::Pit::new()) };
+//! sim.enable_clock(sim::Clock::Pit);
+//! pit.enable(true);
+//!
+//! pit::set_handler(0, || { /* called every 10ms */ });
+//! let channel = pit.channel(0);
+//! channel.set_period(time::Duration::from_millis(10)).unwrap();
+//! channel.enable_interrupt();
+//! channel.start();
+//! ```
+//!
+//! On the teensy 3.5 and 3.6 a channel can be chained to the previous one: it is then
+//! decremented each time the previous channel expires, making a 64 bits timer out of two
+//! channels. The lifetime timer uses this to read the channels 0 and 1 as a single 64 bits
+//! counter. The PIT of the teensy 3.0, 3.1 and 3.2 can't chain and has no lifetime timer.
+//!
+//! The interrupt of a chained channel fires when the whole 64 bits timer expires:
+//! ```rust
+//! // the channel 0 expires every second and the channel 1 counts these expirations
+//! pit.channel(0).set_period(time::Duration::from_secs(1)).unwrap();
+//! pit.channel(1).set_load_value(600 - 1);
+//! pit.channel(1).set_chained(true);
+//! pit::set_handler(1, || { /* called every 10 minutes */ });
+//! pit.channel(1).enable_interrupt();
+//! pit.channel(1).start();
+//! pit.channel(0).start();
+//! ```
+
+use bit_field::BitField;
+use core::time::Duration;
+use volatile::Volatile;
+
+const PIT_BASE_PTR: u32 = 0x4003_7000;
+/// Vector of the channel 0 interrupt, the others follow
+const PIT_CH0_VECTOR: usize = crate::board::vectors::PIT_CH0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The period must be at least one bus cycle and fit in 32 bits of bus cycles
+    InvalidPeriod,
+}
+
+static mut HANDLERS: [Option<fn()>; 4] = [None; 4];
+
+#[repr(C, packed)]
+pub struct Channel {
+    ldval: Volatile<u32>,
+    cval: Volatile<u32>,
+    tctrl: Volatile<u32>,
+    tflg: Volatile<u32>,
+}
+
+#[repr(C, packed)]
+pub struct Pit {
+    mcr: Volatile<u32>,
+    _pad0: Volatile<[u32; 55]>,
+    /// Reserved on the teensy 3.0, 3.1 and 3.2
+    ltmr64h: Volatile<u32>,
+    ltmr64l: Volatile<u32>,
+    _pad1: Volatile<[u32; 6]>,
+    channels: [Channel; 4],
+}
+
+impl Pit {
+    /// The PIT clock gate (`sim::Clock::Pit`) must be enabled before using it.
+    pub unsafe fn new() -> &'static mut Pit {
+        &mut *(PIT_BASE_PTR as *mut Pit)
+    }
+
+    /// Enable the PIT module. If `freeze_in_debug` is set the timers stop while the core is
+    /// halted by a debugger.
+    pub fn enable(&mut self, freeze_in_debug: bool) {
+        self.mcr.update(|mcr| {
+            mcr.set_bit(1, false);
+            mcr.set_bit(0, freeze_in_debug);
+        });
+    }
+
+    /// Disable the PIT module, all the timers stop.
+    pub fn disable(&mut self) {
+        self.mcr.update(|mcr| {
+            mcr.set_bit(1, true);
+        });
+    }
+
+    pub fn channel(&mut self, n: usize) -> &mut Channel {
+        assert!(n < 4, "The PIT only has 4 channels");
+        &mut self.channels[n]
+    }
+
+    /// Start the lifetime timer, using the channels 0 and 1 chained together. It counts the
+    /// bus cycles elapsed since it was started.
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+    pub fn start_lifetime_timer(&mut self) {
+        let (low, high) = self.channels.split_at_mut(1);
+        let (low, high) = (&mut low[0], &mut high[0]);
+        low.stop();
+        high.stop();
+        high.set_load_value(u32::MAX);
+        high.set_chained(true);
+        high.start();
+        low.set_load_value(u32::MAX);
+        low.start();
+    }
+
+    /// Read the lifetime timer, as the number of bus cycles elapsed since
+    /// `start_lifetime_timer`.
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+    pub fn lifetime(&self) -> u64 {
+        // Reading the high part latch the low part
+        let high = self.ltmr64h.read();
+        let low = self.ltmr64l.read();
+        u64::MAX - ((high as u64) << 32 | low as u64)
+    }
+}
+
+impl Channel {
+    /// Set the time between two expirations of the timer. Takes effect when the timer expires,
+    /// or on the next `start`.
+    pub fn set_period(&mut self, period: Duration) -> Result<(), Error> {
+        let bus = crate::mcg::F_BUS as u64;
+        let cycles = period.as_secs().saturating_mul(bus)
+            + period.subsec_nanos() as u64 * bus / 1_000_000_000;
+        if cycles == 0 || cycles > u32::MAX as u64 + 1 {
+            return Err(Error::InvalidPeriod);
+        }
+        self.set_load_value((cycles - 1) as u32);
+        Ok(())
+    }
+
+    /// Set the number of expirations per second.
+    pub fn set_frequency(&mut self, frequency: u32) -> Result<(), Error> {
+        if frequency == 0 || frequency > crate::mcg::F_BUS {
+            return Err(Error::InvalidPeriod);
+        }
+        self.set_load_value(crate::mcg::F_BUS / frequency - 1);
+        Ok(())
+    }
+
+    /// The timer expires every `value + 1` bus cycles.
+    pub fn set_load_value(&mut self, value: u32) {
+        self.ldval.write(value);
+    }
+
+    /// The current value of the down counter.
+    pub fn current_value(&self) -> u32 {
+        self.cval.read()
+    }
+
+    /// Start the timer, it is reloaded with its load value.
+    pub fn start(&mut self) {
+        self.tctrl.update(|tctrl| {
+            tctrl.set_bit(0, true);
+        });
+    }
+
+    pub fn stop(&mut self) {
+        self.tctrl.update(|tctrl| {
+            tctrl.set_bit(0, false);
+        });
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.tctrl.read().get_bit(0)
+    }
+
+    /// Decrement this timer only when the previous channel expires. Not available on the
+    /// channel 0.
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+    pub fn set_chained(&mut self, chained: bool) {
+        self.tctrl.update(|tctrl| {
+            tctrl.set_bit(2, chained);
+        });
+    }
+
+    /// Interrupt on each expiration, the handler registered with `set_handler` is called.
+    pub fn enable_interrupt(&mut self) {
+        self.tctrl.update(|tctrl| {
+            tctrl.set_bit(1, true);
+        });
+        crate::interrupts::enable(PIT_CH0_VECTOR + self.index());
+    }
+
+    pub fn disable_interrupt(&mut self) {
+        self.tctrl.update(|tctrl| {
+            tctrl.set_bit(1, false);
+        });
+        crate::interrupts::disable(PIT_CH0_VECTOR + self.index());
+    }
+
+    /// TIF: the timer expired since the flag was cleared.
+    pub fn has_expired(&self) -> bool {
+        self.tflg.read().get_bit(0)
+    }
+
+    pub fn clear_flag(&mut self) {
+        // write one to clear
+        self.tflg.write(1);
+    }
+
+    fn index(&self) -> usize {
+        (self as *const Channel as u32 - PIT_BASE_PTR - 0x100) as usize / 0x10
+    }
+}
+
+/// Register the function called when the channel `n` expires. The flag is cleared for you.
+pub fn set_handler(n: usize, handler: fn()) {
+    assert!(n < 4, "The PIT only has 4 channels");
+    crate::interrupts::free(|| unsafe {
+        HANDLERS[n] = Some(handler);
+    });
+}
+
+/// Called from the interrupt vector of the channel `n`.
+pub(crate) unsafe fn on_interrupt(n: usize) {
+    Pit::new().channel(n).clear_flag();
+    if let Some(handler) = HANDLERS[n] {
+        handler();
+    }
+}
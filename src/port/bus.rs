@@ -0,0 +1,159 @@
+use super::pin::Pin;
+use super::port::{Error, PortName};
+use volatile::Volatile;
+
+/// The 32 bits GPIO registers of a port, each bit is a pin.
+#[repr(C, packed)]
+struct GpioRegisters {
+    pdor: Volatile<u32>,
+    psor: Volatile<u32>,
+    pcor: Volatile<u32>,
+    ptor: Volatile<u32>,
+    pdir: Volatile<u32>,
+    pddr: Volatile<u32>,
+}
+
+/// Up to 32 pins of the same port read and written together. The bit `n` of the values is the
+/// pin `n` of the list given at creation.
+///
+/// Unlike `Gpio` it uses the 32 bits registers of the port, so all the pins change in the same
+/// cycle.
+/// ```rust
+/// // an 8 bits bus on PTD0 to PTD7
+/// let mut bus = unsafe { port::GpioBus::from_numbers([2, 14, 7, 8, 6, 20, 21, 5]) }.unwrap();
+/// bus.output();
+/// bus.write(0xA5);
+/// ```
+pub struct GpioBus<const N: usize> {
+    gpio: *mut GpioRegisters,
+    pins: [Pin; N],
+    /// The pins of the bus in the port
+    mask: u32,
+    /// When the pins are consecutive in the port, the position of the first one
+    shift: Option<usize>,
+}
+
+impl<const N: usize> GpioBus<N> {
+    /// Consume the pins into a bus and put them in gpio mode. All the pins must be on the same
+    /// port.
+    pub fn new(mut pins: [Pin; N]) -> Result<GpioBus<N>, Error> {
+        if N == 0 || N > 32 {
+            return Err(Error::InvalidBus);
+        }
+
+        let portname = pins[0].portname;
+        let mut mask = 0;
+        for pin in pins.iter() {
+            if pin.portname != portname || mask & 1 << pin.id != 0 {
+                return Err(Error::InvalidBus);
+            }
+            mask |= 1 << pin.id;
+        }
+
+        let first = pins[0].id;
+        let shift = if pins.iter().enumerate().all(|(n, pin)| pin.id == first + n) {
+            Some(first)
+        } else {
+            None
+        };
+
+        for pin in pins.iter_mut() {
+            unsafe { pin.set_pin_mode(1) };
+        }
+
+        let gpio = match portname {
+            PortName::A => 0x400F_F000 as *mut GpioRegisters,
+            PortName::B => 0x400F_F040 as *mut GpioRegisters,
+            PortName::C => 0x400F_F080 as *mut GpioRegisters,
+            PortName::D => 0x400F_F0C0 as *mut GpioRegisters,
+            PortName::E => 0x400F_F100 as *mut GpioRegisters,
+        };
+
+        Ok(GpioBus {
+            gpio,
+            pins,
+            mask,
+            shift,
+        })
+    }
+
+    /// Create a bus from the teensy pin numbers, see `Pin::new`.
+    pub unsafe fn from_numbers(numbers: [usize; N]) -> Result<GpioBus<N>, Error> {
+        let mut pins = numbers.iter().map(|n| Pin::new(*n));
+        GpioBus::new([(); N].map(|_| pins.next().unwrap()))
+    }
+
+    /// Switch all the pins of the bus in output mode.
+    pub fn output(&mut self) {
+        let mask = self.mask;
+        crate::interrupts::free(|| unsafe {
+            (*self.gpio).pddr.update(|pddr| *pddr |= mask);
+        });
+    }
+
+    /// Switch all the pins of the bus in input mode.
+    pub fn input(&mut self) {
+        let mask = self.mask;
+        crate::interrupts::free(|| unsafe {
+            (*self.gpio).pddr.update(|pddr| *pddr &= !mask);
+        });
+    }
+
+    /// Drive all the pins at once.
+    pub fn write(&mut self, value: u32) {
+        let (mask, bits) = (self.mask, self.to_port(value));
+        // a single write of the output register, the other pins of the port keep their value
+        crate::interrupts::free(|| unsafe {
+            (*self.gpio).pdor.update(|pdor| *pdor = *pdor & !mask | bits);
+        });
+    }
+
+    /// Set the pins whose bit is one, leave the others unchanged.
+    pub fn set_high(&mut self, value: u32) {
+        unsafe { (*self.gpio).psor.write(self.to_port(value)) };
+    }
+
+    /// Clear the pins whose bit is one, leave the others unchanged.
+    pub fn set_low(&mut self, value: u32) {
+        unsafe { (*self.gpio).pcor.write(self.to_port(value)) };
+    }
+
+    /// Toggle the pins whose bit is one, leave the others unchanged.
+    pub fn toggle(&mut self, value: u32) {
+        unsafe { (*self.gpio).ptor.write(self.to_port(value)) };
+    }
+
+    /// Read all the pins at once.
+    pub fn read(&self) -> u32 {
+        self.from_port(unsafe { (*self.gpio).pdir.read() })
+    }
+
+    /// Get the pins back.
+    pub fn free(self) -> [Pin; N] {
+        self.pins
+    }
+
+    fn to_port(&self, value: u32) -> u32 {
+        match self.shift {
+            Some(shift) => value << shift & self.mask,
+            None => self
+                .pins
+                .iter()
+                .enumerate()
+                .filter(|(n, _)| value & 1 << n != 0)
+                .fold(0, |bits, (_, pin)| bits | 1 << pin.id),
+        }
+    }
+
+    fn from_port(&self, bits: u32) -> u32 {
+        match self.shift {
+            Some(shift) => (bits & self.mask) >> shift,
+            None => self
+                .pins
+                .iter()
+                .enumerate()
+                .filter(|(_, pin)| bits & 1 << pin.id != 0)
+                .fold(0, |value, (n, _)| value | 1 << n),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use bit_field::BitField;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pull {
+    Down,
+    Up,
+}
+
+/// The configuration held by the lower half of a Pin Control Register, plus the digital filter
+/// enable of the port. See the `set_pin_*` methods of `Pin` for the meaning of each field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PinConfig {
+    /// The ALT number, from 0 to 7
+    pub mux: u32,
+    pub pull: Option<Pull>,
+    /// SRE
+    pub slow_slew_rate: bool,
+    /// PFE
+    pub passive_filter: bool,
+    /// ODE
+    pub open_drain: bool,
+    /// DSE
+    pub high_drive_strength: bool,
+    /// DFER, see `Port::set_debounce`
+    pub digital_filter: bool,
+    /// LK, once applied the configuration can't be changed until the next reset
+    pub locked: bool,
+}
+
+impl PinConfig {
+    /// Decode a PCR value, and the digital filter enable bit of the pin.
+    pub fn from_pcr(pcr: u32, digital_filter: bool) -> PinConfig {
+        PinConfig {
+            mux: pcr.get_bits(8..=10),
+            pull: match (pcr.get_bit(1), pcr.get_bit(0)) {
+                (false, _) => None,
+                (true, false) => Some(Pull::Down),
+                (true, true) => Some(Pull::Up),
+            },
+            slow_slew_rate: pcr.get_bit(2),
+            passive_filter: pcr.get_bit(4),
+            open_drain: pcr.get_bit(5),
+            high_drive_strength: pcr.get_bit(6),
+            digital_filter,
+            locked: pcr.get_bit(15),
+        }
+    }
+
+    /// The lower 16 bits of the PCR.
+    pub fn pcr_bits(&self) -> u16 {
+        let mut pcr: u16 = 0;
+        pcr.set_bit(0, self.pull == Some(Pull::Up));
+        pcr.set_bit(1, self.pull.is_some());
+        pcr.set_bit(2, self.slow_slew_rate);
+        pcr.set_bit(4, self.passive_filter);
+        pcr.set_bit(5, self.open_drain);
+        pcr.set_bit(6, self.high_drive_strength);
+        pcr.set_bits(8..=10, (self.mux & 0b111) as u16);
+        pcr.set_bit(15, self.locked);
+        pcr
+    }
+}
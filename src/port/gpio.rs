@@ -1,3 +1,4 @@
+use super::interrupt::{self, Edge};
 use super::pin::Pin;
 use super::port::PortName;
 use volatile::Volatile;
@@ -14,8 +15,21 @@ struct GpioBitband {
 
 use core::marker::PhantomData;
 pub struct Uninitialized {}
-pub struct Input {}
+/// Readable pin, `P` is its pull resistor
+pub struct Input<P = AsConfigured> {
+    _pull: PhantomData<P>,
+}
+/// Writable pin, which can also read back the level it drives
 pub struct Output {}
+/// Open drain pin, readable and writable. Driving it high releases the line, which can then be
+/// pulled low by another device
+pub struct OpenDrain {}
+
+/// The pull resistor was left as configured on the `Pin`
+pub struct AsConfigured {}
+pub struct Floating {}
+pub struct PullUp {}
+pub struct PullDown {}
 
 /// Chapter 49: General-Purpose Input/Output (GPIO)
 /// doc/teensy_3.2.pdf - Page 1331
@@ -46,31 +60,117 @@ impl Gpio<Uninitialized> {
 }
 
 impl<AnyState> Gpio<AnyState> {
-    /// Switch the pin in input mode (can read but not write)
+    /// Lock the configuration of the pin until the next reset, see `Pin::lock`. The direction
+    /// and the level are not locked.
+    pub fn lock(&mut self) {
+        self.pin.lock();
+    }
+
+    /// Switch the pin in input mode (can read but not write), the pull resistor is left as
+    /// configured on the `Pin`
     /// See `input` function
     ///
     /// *This function can be implemented with a single write,
     /// eliminating the potential race condition. Thus its use is safe*
-    pub fn input(self) -> Gpio<Input> {
-        unsafe {
-            (*self.gpio).pddr[self.pin.id].write(0);
-        }
-        Gpio {
-            gpio: self.gpio,
-            pin: self.pin,
-            _phantom: Default::default(),
-        }
+    pub fn input(mut self) -> Gpio<Input> {
+        self.set_direction(false);
+        self.into_state()
+    }
+
+    /// Switch the pin in input mode without pull resistor
+    pub fn into_floating_input(mut self) -> Gpio<Input<Floating>> {
+        self.set_pull(false, false);
+        self.set_direction(false);
+        self.into_state()
     }
 
-    /// Switch the pin in output mode (can write but not read)
+    /// Switch the pin in input mode with the internal pullup resistor
+    pub fn into_pull_up_input(mut self) -> Gpio<Input<PullUp>> {
+        self.set_pull(true, true);
+        self.set_direction(false);
+        self.into_state()
+    }
+
+    /// Switch the pin in input mode with the internal pulldown resistor
+    pub fn into_pull_down_input(mut self) -> Gpio<Input<PullDown>> {
+        self.set_pull(true, false);
+        self.set_direction(false);
+        self.into_state()
+    }
+
+    /// Switch the pin in output mode (can write and read back the output), the open drain is
+    /// left as configured on the `Pin`
     /// See `output` function
     ///
     /// *This function can be implemented with a single write,
     /// eliminating the potential race condition. Thus its use is safe*
-    pub fn output(self) -> Gpio<Output> {
+    pub fn output(mut self) -> Gpio<Output> {
+        self.set_direction(true);
+        self.into_state()
+    }
+
+    /// Switch the pin in output mode, driving both levels (open drain disabled)
+    pub fn into_push_pull_output(mut self) -> Gpio<Output> {
+        unsafe { self.pin.set_pin_ode(false) };
+        self.set_direction(true);
+        self.into_state()
+    }
+
+    /// Switch the pin in open drain mode (can write and read), with the internal pullup
+    /// resistor if `pull_up` is set. The line is released before switching.
+    pub fn into_open_drain(mut self, pull_up: bool) -> Gpio<OpenDrain> {
+        unsafe {
+            (*self.gpio).psor[self.pin.id].write(1);
+            self.pin.set_pin_ode(true);
+        }
+        self.set_pull(pull_up, true);
+        self.set_direction(true);
+        self.into_state()
+    }
+
+    /// Get the pin back, in its current configuration.
+    pub fn into_pin(self) -> Pin {
+        self.pin
+    }
+
+    /// Get the pin back, after restoring the reset configuration of the gpio: input, no pull
+    /// resistor, no open drain and no interrupt. The pin is left in gpio mode.
+    pub fn free(mut self) -> Pin {
         unsafe {
-            (*self.gpio).pddr[self.pin.id].write(1);
+            self.pin.set_pin_irqc(0);
+            self.pin.set_pin_ode(false);
         }
+        self.pin.clear_interrupt();
+        interrupt::set_handler(self.pin.portname, self.pin.id, None);
+        self.set_pull(false, false);
+        self.set_direction(false);
+        self.pin
+    }
+
+    /// The level read on the pin.
+    fn is_high(&self) -> bool {
+        unsafe { (*self.gpio).pdir[self.pin.id].read() != 0 }
+    }
+
+    /// The level written in the output register.
+    fn is_set_high(&self) -> bool {
+        unsafe { (*self.gpio).pdor[self.pin.id].read() != 0 }
+    }
+
+    fn set_direction(&mut self, output: bool) {
+        unsafe {
+            (*self.gpio).pddr[self.pin.id].write(output as u32);
+        }
+    }
+
+    fn set_pull(&mut self, enable: bool, up: bool) {
+        unsafe {
+            self.pin.set_pin_ps(up);
+            self.pin.set_pin_pe(enable);
+        }
+    }
+
+    fn into_state<State>(self) -> Gpio<State> {
         Gpio {
             gpio: self.gpio,
             pin: self.pin,
@@ -79,9 +179,33 @@ impl<AnyState> Gpio<AnyState> {
     }
 }
 
+impl<P> Gpio<Input<P>> {
+    /// Call `handler` from the port interrupt vector each time `edge` is detected on the pin.
+    /// The interrupt flag is cleared before the handler is called.
+    /// ```rust
+    /// let mut button = unsafe { make_pin!(6) }.make_gpio().input();
+    /// button.enable_interrupt(port::Edge::Falling, || { /* pressed */ });
+    /// ```
+    pub fn enable_interrupt(&mut self, edge: Edge, handler: fn()) {
+        interrupt::set_handler(self.pin.portname, self.pin.id, Some(handler));
+        unsafe {
+            self.pin.clear_interrupt();
+            self.pin.set_pin_irqc(edge as u32);
+        }
+    }
+
+    pub fn disable_interrupt(&mut self) {
+        unsafe {
+            self.pin.set_pin_irqc(0);
+        }
+        self.pin.clear_interrupt();
+        interrupt::set_handler(self.pin.portname, self.pin.id, None);
+    }
+}
+
 use embedded_hal::digital;
 
-impl digital::InputPin for Gpio<Input> {
+impl<P> digital::InputPin for Gpio<Input<P>> {
     type Error = !;
 
     /// **Before use, call the `input` function**
@@ -95,8 +219,7 @@ impl digital::InputPin for Gpio<Input> {
     /// *This function can be implemented with a single write,
     /// eliminating the potential race condition. Thus its use is safe*
     fn try_is_high(&self) -> Result<bool, Self::Error> {
-        let v = unsafe { (*self.gpio).pdir[self.pin.id].read() };
-        Ok(v != 0)
+        Ok(self.is_high())
     }
 
     /// **Before use, call the `input` function**
@@ -110,8 +233,7 @@ impl digital::InputPin for Gpio<Input> {
     /// *This function can be implemented with a single write,
     /// eliminating the potential race condition. Thus its use is safe*
     fn try_is_low(&self) -> Result<bool, Self::Error> {
-        let v = unsafe { (*self.gpio).pdir[self.pin.id].read() };
-        Ok(v == 0)
+        Ok(!self.is_high())
     }
 }
 
@@ -173,3 +295,69 @@ impl digital::ToggleableOutputPin for Gpio<Output> {
         Ok(())
     }
 }
+
+impl digital::StatefulOutputPin for Gpio<Output> {
+    /// Read back the level written, which can differ from the level of the pin if it is
+    /// overloaded
+    fn try_is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_set_high())
+    }
+
+    fn try_is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set_high())
+    }
+}
+
+impl digital::InputPin for Gpio<OpenDrain> {
+    type Error = !;
+
+    /// The level of the line, low if another device pulls it down
+    fn try_is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_high())
+    }
+
+    fn try_is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high())
+    }
+}
+
+impl digital::OutputPin for Gpio<OpenDrain> {
+    type Error = !;
+
+    /// Release the line
+    fn try_set_high(&mut self) -> Result<(), Self::Error> {
+        unsafe {
+            (*self.gpio).psor[self.pin.id].write(1);
+        }
+        Ok(())
+    }
+
+    /// Pull the line low
+    fn try_set_low(&mut self) -> Result<(), Self::Error> {
+        unsafe {
+            (*self.gpio).pcor[self.pin.id].write(1);
+        }
+        Ok(())
+    }
+}
+
+impl digital::StatefulOutputPin for Gpio<OpenDrain> {
+    fn try_is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_set_high())
+    }
+
+    fn try_is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set_high())
+    }
+}
+
+impl digital::ToggleableOutputPin for Gpio<OpenDrain> {
+    type Error = !;
+
+    fn try_toggle(&mut self) -> Result<(), Self::Error> {
+        unsafe {
+            (*self.gpio).ptor[self.pin.id].write(1);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use super::port::{Port, PortName};
+
+/// Vector of the port A interrupt, the other ports follow
+const PORTA_VECTOR: usize = crate::board::vectors::PORTA;
+
+/// The condition triggering a pin interrupt, the values are the IRQC field of the PCR.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// Interrupt as long as the pin is low, the handler must change the condition or disable
+    /// the interrupt
+    LevelLow = 0b1000,
+    Rising = 0b1001,
+    Falling = 0b1010,
+    Both = 0b1011,
+    /// Interrupt as long as the pin is high, see `LevelLow`
+    LevelHigh = 0b1100,
+}
+
+static mut HANDLERS: [[Option<fn()>; 32]; 5] = [[None; 32]; 5];
+
+/// Register the handler of a pin and enable the interrupt of its port.
+pub(super) fn set_handler(port: PortName, pin: usize, handler: Option<fn()>) {
+    crate::interrupts::free(|| unsafe {
+        HANDLERS[port as usize][pin] = handler;
+    });
+    if handler.is_some() {
+        crate::interrupts::enable(PORTA_VECTOR + port as usize);
+    }
+}
+
+/// Called from the interrupt vector of the port, clear the flags and call the handlers of the
+/// pins that triggered.
+pub(crate) unsafe fn on_interrupt(name: PortName) {
+    let flags = Port::new(name).take_interrupt_flags();
+    for pin in 0..32 {
+        if flags & 1 << pin == 0 {
+            continue;
+        }
+        if let Some(handler) = HANDLERS[name as usize][pin] {
+            handler();
+        }
+    }
+}
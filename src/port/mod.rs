@@ -34,8 +34,14 @@
 //! }
 //! ```
 //!
-//! If you are here it's probably because you want to use some pins, here is the step you need to
-//! follow:
+//! If you are here it's probably because you want to use some pins. The safe way is to take all
+//! the pins once with `Pins::take`, see the `pins` module:
+//! ```rust
+//! let pins = port::Pins::take().unwrap();
+//! let mut led = pins.p13.make_gpio().output();
+//! ```
+//!
+//! Otherwise here is the step you need to follow:
 //! 1. Identifiying in which port the pin you want to use is located, try looking at this
 //!    documents: [teensy schematic representation](https://github.com/irevoire/teensy/blob/master/doc/schematic.gif)
 //!    For example imagine we want to lighten up the led. On this document we can see that the led
@@ -55,13 +61,23 @@
 //! gpio.high();
 //! ```
 
+pub use self::bus::GpioBus;
+pub use self::config::{PinConfig, Pull};
 pub use self::pin::Pin;
-pub use self::port::Port;
+pub use self::pins::{Led, PortA, PortB, PortC, PortD, PortE, PortId, Pins};
+pub use self::port::{filter_width, Error, FilterClock, Port};
 pub use self::port::PortName;
-pub use self::gpio::Gpio;
+pub use self::gpio::{AsConfigured, Floating, Gpio, Input, OpenDrain, Output, PullDown, PullUp};
+pub use self::interrupt::Edge;
+pub(crate) use self::interrupt::on_interrupt;
 
+mod bus;
+mod config;
 mod gpio;
+mod interrupt;
+pub mod mux;
 mod pin;
+pub mod pins;
 mod port;
 
 pub mod macros;
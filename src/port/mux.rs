@@ -0,0 +1,201 @@
+//! # Pin multiplexing
+//! Each pin can carry up to 8 signals, selected by the MUX field of its PCR. The typed pins (see
+//! the `pins` module) keep the selected signal in their type, `Pin<PortB, Alt3, 16>` is the pin 0
+//! muxed to UART0_RX.
+//!
+//! The functions of the peripherals are traits, implemented only for the pins supporting them
+//! according to the signal multiplexing table of the MK20DX256 (chapter 10.3). A driver asking for
+//! a `UartRxPin<Uart0>` can't be given a wrong pin or a wrong mux number:
+//! ```rust
+//! let pins = port::Pins::take().unwrap();
+//! let uart = uart::UART::uart0(pins.p21, pins.p5).unwrap(); // PTD6 and PTD7, ALT3
+//! ```
+
+use super::pins::{Pin, PortA, PortB, PortC, PortD, PortE};
+
+/// A value of the MUX field.
+pub trait MuxMode {
+    const ALT: u32;
+}
+
+/// The state of a pin taken with `Pins::take`, the mux is left as the bootloader set it.
+pub struct Unconfigured;
+/// Analog pin or disabled
+pub struct Alt0;
+/// Gpio
+pub struct Alt1Gpio;
+pub struct Alt2;
+pub struct Alt3;
+pub struct Alt4;
+pub struct Alt5;
+pub struct Alt6;
+pub struct Alt7;
+
+impl MuxMode for Alt0 {
+    const ALT: u32 = 0;
+}
+impl MuxMode for Alt1Gpio {
+    const ALT: u32 = 1;
+}
+impl MuxMode for Alt2 {
+    const ALT: u32 = 2;
+}
+impl MuxMode for Alt3 {
+    const ALT: u32 = 3;
+}
+impl MuxMode for Alt4 {
+    const ALT: u32 = 4;
+}
+impl MuxMode for Alt5 {
+    const ALT: u32 = 5;
+}
+impl MuxMode for Alt6 {
+    const ALT: u32 = 6;
+}
+impl MuxMode for Alt7 {
+    const ALT: u32 = 7;
+}
+
+// ===== peripherals =====
+pub struct Uart0;
+pub struct Uart1;
+pub struct Uart2;
+#[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+pub struct Uart3;
+#[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+pub struct Uart4;
+pub struct Spi0;
+pub struct I2c0;
+pub struct I2c1;
+pub struct Ftm0;
+pub struct Ftm1;
+pub struct Ftm2;
+
+// ===== functions =====
+// `mux` selects the signal and return the pin in its new state.
+
+pub trait UartRxPin<U> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+pub trait UartTxPin<U> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+pub trait SpiSckPin<S> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+/// Master out, slave in
+pub trait SpiSoutPin<S> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+/// Master in, slave out
+pub trait SpiSinPin<S> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+/// The chip select `CS` (PCSn)
+pub trait SpiCsPin<S, const CS: usize> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+pub trait I2cSclPin<I> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+pub trait I2cSdaPin<I> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+/// The channel `CH` of a FlexTimer, for PWM or input capture
+pub trait FtmChannelPin<F, const CH: usize> {
+    type Muxed: Into<super::Pin>;
+    fn mux(self) -> Self::Muxed;
+}
+
+macro_rules! functions {
+    ($($function:ident<$($param:tt),+>: $port:ident $id:literal $mode:ident;)+) => {
+        $(
+            impl<M> $function<$($param),+> for Pin<$port, M, $id> {
+                type Muxed = Pin<$port, $mode, $id>;
+
+                fn mux(self) -> Self::Muxed {
+                    self.into_mode()
+                }
+            }
+        )+
+    };
+}
+
+// The pins available on all the boards
+functions! {
+    UartRxPin<Uart0>: PortB 16 Alt3;
+    UartRxPin<Uart0>: PortD 6 Alt3;
+    UartTxPin<Uart0>: PortB 17 Alt3;
+    UartTxPin<Uart0>: PortD 7 Alt3;
+    UartRxPin<Uart1>: PortC 3 Alt3;
+    UartRxPin<Uart1>: PortE 1 Alt3;
+    UartTxPin<Uart1>: PortC 4 Alt3;
+    UartTxPin<Uart1>: PortE 0 Alt3;
+    UartRxPin<Uart2>: PortD 2 Alt3;
+    UartTxPin<Uart2>: PortD 3 Alt3;
+
+    SpiSckPin<Spi0>: PortC 5 Alt2;
+    SpiSckPin<Spi0>: PortD 1 Alt2;
+    SpiSoutPin<Spi0>: PortC 6 Alt2;
+    SpiSoutPin<Spi0>: PortD 2 Alt2;
+    SpiSinPin<Spi0>: PortC 7 Alt2;
+    SpiSinPin<Spi0>: PortD 3 Alt2;
+    SpiCsPin<Spi0, 0>: PortC 4 Alt2;
+    SpiCsPin<Spi0, 0>: PortD 0 Alt2;
+    SpiCsPin<Spi0, 1>: PortC 3 Alt2;
+    SpiCsPin<Spi0, 1>: PortD 4 Alt2;
+    SpiCsPin<Spi0, 2>: PortC 2 Alt2;
+    SpiCsPin<Spi0, 2>: PortD 5 Alt2;
+    SpiCsPin<Spi0, 3>: PortC 1 Alt2;
+    SpiCsPin<Spi0, 3>: PortD 6 Alt2;
+    SpiCsPin<Spi0, 4>: PortC 0 Alt2;
+
+    I2cSclPin<I2c0>: PortB 0 Alt2;
+    I2cSclPin<I2c0>: PortB 2 Alt2;
+    I2cSdaPin<I2c0>: PortB 1 Alt2;
+    I2cSdaPin<I2c0>: PortB 3 Alt2;
+    I2cSclPin<I2c1>: PortC 10 Alt2;
+    I2cSdaPin<I2c1>: PortC 11 Alt2;
+
+    FtmChannelPin<Ftm0, 0>: PortC 1 Alt4;
+    FtmChannelPin<Ftm0, 1>: PortC 2 Alt4;
+    FtmChannelPin<Ftm0, 2>: PortC 3 Alt4;
+    FtmChannelPin<Ftm0, 3>: PortC 4 Alt4;
+    FtmChannelPin<Ftm0, 4>: PortD 4 Alt4;
+    FtmChannelPin<Ftm0, 5>: PortD 5 Alt4;
+    FtmChannelPin<Ftm0, 6>: PortD 6 Alt4;
+    FtmChannelPin<Ftm0, 7>: PortD 7 Alt4;
+    FtmChannelPin<Ftm1, 0>: PortA 12 Alt3;
+    FtmChannelPin<Ftm1, 0>: PortB 0 Alt3;
+    FtmChannelPin<Ftm1, 1>: PortA 13 Alt3;
+    FtmChannelPin<Ftm1, 1>: PortB 1 Alt3;
+    FtmChannelPin<Ftm2, 0>: PortB 18 Alt3;
+    FtmChannelPin<Ftm2, 1>: PortB 19 Alt3;
+}
+
+// The pins only available on the teensy 3.5 and 3.6
+#[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+functions! {
+    UartRxPin<Uart0>: PortA 15 Alt3;
+    UartTxPin<Uart0>: PortA 14 Alt3;
+    UartRxPin<Uart3>: PortB 10 Alt3;
+    UartTxPin<Uart3>: PortB 11 Alt3;
+    UartRxPin<Uart4>: PortE 25 Alt3;
+    UartTxPin<Uart4>: PortE 24 Alt3;
+}
@@ -1,5 +1,6 @@
+use super::config::PinConfig;
 use super::gpio::{Gpio, Uninitialized};
-use super::port::{Port, PortName};
+use super::port::{Error, Port, PortName};
 use bit_field::BitField;
 use volatile::Volatile;
 
@@ -12,46 +13,11 @@ pub struct Pin {
 impl Pin {
     /// Create a pin from the pin number indicated in the
     /// [schematic representation of the teensy](https://github.com/irevoire/teensy/blob/master/doc/schematic.gif)
-    /// Will panic if the number is more than 33
+    /// Will panic if the number is not a pin of the board
     pub unsafe fn new(number: usize) -> Self {
-        match number {
-            0 => Port::new(PortName::B).pin(16),
-            1 => Port::new(PortName::B).pin(17),
-            2 => Port::new(PortName::D).pin(0),
-            3 => Port::new(PortName::A).pin(12),
-            4 => Port::new(PortName::A).pin(13),
-            5 => Port::new(PortName::D).pin(7),
-            6 => Port::new(PortName::D).pin(4),
-            7 => Port::new(PortName::D).pin(2),
-            8 => Port::new(PortName::D).pin(3),
-            9 => Port::new(PortName::C).pin(3),
-            10 => Port::new(PortName::C).pin(4),
-            11 => Port::new(PortName::C).pin(6),
-            12 => Port::new(PortName::C).pin(7),
-
-            13 => Port::new(PortName::C).pin(5),
-            14 => Port::new(PortName::D).pin(1),
-            15 => Port::new(PortName::C).pin(0),
-            16 => Port::new(PortName::B).pin(0),
-            17 => Port::new(PortName::B).pin(1),
-            18 => Port::new(PortName::B).pin(3),
-            19 => Port::new(PortName::B).pin(2),
-            20 => Port::new(PortName::D).pin(5),
-            21 => Port::new(PortName::D).pin(6),
-            22 => Port::new(PortName::C).pin(1),
-            23 => Port::new(PortName::C).pin(2),
-
-            24 => Port::new(PortName::A).pin(5),
-            25 => Port::new(PortName::B).pin(19),
-            26 => Port::new(PortName::E).pin(1),
-            27 => Port::new(PortName::C).pin(9),
-            28 => Port::new(PortName::C).pin(8),
-            29 => Port::new(PortName::C).pin(10),
-            30 => Port::new(PortName::C).pin(11),
-            31 => Port::new(PortName::E).pin(0),
-            32 => Port::new(PortName::B).pin(18),
-            33 => Port::new(PortName::A).pin(4),
-            _ => panic!("Bad pin number!"),
+        match crate::board::pin_location(number) {
+            Some((portname, id)) => Port::new(portname).pin(id),
+            None => panic!("Bad pin number!"),
         }
     }
 
@@ -200,6 +166,90 @@ impl Pin {
         self
     }
 
+    /// Digital Filter Enable
+    ///
+    /// - `false`: Digital filter is disabled on the corresponding pin.
+    /// - `true`: Digital filter is enabled on the corresponding pin, if the pin is configured
+    /// as a digital input. The filter clock and width are configured on the port, see
+    /// `Port::set_debounce`.
+    pub unsafe fn set_pin_dfe(&mut self, mode: bool) {
+        Port::new(self.portname).enable_filter(self.id, mode);
+    }
+
+    /// Refer to the `set_pin_dfe` method
+    pub unsafe fn with_pin_dfe(mut self, mode: bool) -> Self {
+        self.set_pin_dfe(mode);
+        self
+    }
+
+    /// Interrupt Configuration
+    ///
+    /// Set the IRQC field, `0` disables the interrupt, see `Edge` for the other values. This
+    /// only configures the pin, the handler is registered by `Gpio::enable_interrupt`.
+    pub unsafe fn set_pin_irqc(&mut self, mode: u32) {
+        self.pcr.update(|pcr| {
+            pcr.set_bit(24, false); // don't clear a pending interrupt
+            pcr.set_bits(16..=19, mode & 0b1111);
+        });
+    }
+
+    /// Refer to the `set_pin_irqc` method
+    pub unsafe fn with_pin_irqc(mut self, mode: u32) -> Self {
+        self.set_pin_irqc(mode);
+        self
+    }
+
+    /// Interrupt Status Flag
+    ///
+    /// `true` when the condition configured by `set_pin_irqc` was detected. Cleared by
+    /// `clear_interrupt`, or by the port interrupt vector.
+    pub fn interrupt_pending(&self) -> bool {
+        self.pcr.read().get_bit(24)
+    }
+
+    /// Clear the interrupt status flag
+    pub fn clear_interrupt(&mut self) {
+        // write one to clear
+        self.pcr.update(|pcr| {
+            pcr.set_bit(24, true);
+        });
+    }
+
+    /// Read back the whole configuration of the pin.
+    pub fn config(&self) -> PinConfig {
+        let dfer = unsafe { Port::new(self.portname).filters() };
+        PinConfig::from_pcr(self.pcr.read(), dfer.get_bit(self.id))
+    }
+
+    /// Apply a configuration to the pin, in a single write of the PCR. The interrupt
+    /// configuration is left untouched. Fails, leaving the pin as it is, once the pin is locked.
+    pub unsafe fn set_config(&mut self, config: &PinConfig) -> Result<(), Error> {
+        if self.is_locked() {
+            return Err(Error::Locked);
+        }
+        self.set_pin_dfe(config.digital_filter);
+        self.pcr.update(|pcr| {
+            pcr.set_bit(24, false); // don't clear a pending interrupt
+            pcr.set_bits(0..16, config.pcr_bits() as u32);
+        });
+        Ok(())
+    }
+
+    /// Lock
+    ///
+    /// Prevent any change of the mux, pull, slew rate, filter, open drain and drive strength
+    /// until the next reset. The writes are silently ignored.
+    pub fn lock(&mut self) {
+        self.pcr.update(|pcr| {
+            pcr.set_bit(24, false); // don't clear a pending interrupt
+            pcr.set_bit(15, true);
+        });
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.pcr.read().get_bit(15)
+    }
+
     /// Put the pin in gpio mode and consume the pin into a gpio
     pub fn make_gpio(self) -> Gpio<Uninitialized> {
         unsafe { Gpio::new(self) }
@@ -0,0 +1,190 @@
+//! # Typed pins
+//! Each pin of the teensy is a distinct type, `Pin<PortC, Unconfigured, 5>` for the pin 13, and
+//! `Pins::take` hands out one value per pin only once. The compiler then guarantees that a pin
+//! is owned by a single piece of code, and the drivers consume the pins they use:
+//! ```rust
+//! let pins = port::Pins::take().unwrap();
+//! let mut led = pins.p13.make_gpio().output();
+//! let uart = uart::UART::uart0(pins.p0, pins.p1).unwrap();
+//! ```
+//!
+//! The typed pins can be converted into the runtime `port::Pin` with `erase` to use the lower
+//! level functions.
+
+use super::config::PinConfig;
+use super::gpio::{Gpio, Uninitialized};
+use super::mux::{MuxMode, Unconfigured};
+use super::port::{Error, Port, PortName};
+use core::marker::PhantomData;
+
+/// A port known at compile time.
+pub trait PortId {
+    const NAME: PortName;
+}
+
+pub struct PortA;
+pub struct PortB;
+pub struct PortC;
+pub struct PortD;
+pub struct PortE;
+
+impl PortId for PortA {
+    const NAME: PortName = PortName::A;
+}
+impl PortId for PortB {
+    const NAME: PortName = PortName::B;
+}
+impl PortId for PortC {
+    const NAME: PortName = PortName::C;
+}
+impl PortId for PortD {
+    const NAME: PortName = PortName::D;
+}
+impl PortId for PortE {
+    const NAME: PortName = PortName::E;
+}
+
+/// The pin `N` of the port `P`, muxed in the mode `M` (see the `mux` module). There is only
+/// one value of each pin, see `Pins::take`.
+///
+/// The mode comes before the number because the nightly used by the crate requires the type
+/// parameters to be declared before the const ones.
+pub struct Pin<P, M, const N: usize> {
+    _port: PhantomData<(P, M)>,
+}
+
+/// The pin of the led, the pin 13.
+pub type Led = Pin<PortC, Unconfigured, 5>;
+
+impl<P: PortId, M, const N: usize> Pin<P, M, N> {
+    /// The caller must ensure that there is no other value of this pin.
+    pub(crate) unsafe fn new() -> Self {
+        Pin {
+            _port: PhantomData,
+        }
+    }
+
+    pub fn portname(&self) -> PortName {
+        P::NAME
+    }
+
+    pub fn id(&self) -> usize {
+        N
+    }
+
+    /// Select the signal of the pin.
+    pub fn into_mode<M2: MuxMode>(self) -> Pin<P, M2, N> {
+        unsafe {
+            self.erase().set_pin_mode(M2::ALT);
+            Pin::new()
+        }
+    }
+
+    /// Read back the whole configuration of the pin.
+    pub fn config(&self) -> PinConfig {
+        self.raw().config()
+    }
+
+    /// Apply a configuration to the pin, except its mux which is part of the type. Fails once
+    /// the pin is locked.
+    pub fn set_config(&mut self, config: &PinConfig) -> Result<(), Error> {
+        let mut raw = self.raw();
+        let config = PinConfig {
+            mux: raw.config().mux,
+            ..*config
+        };
+        unsafe { raw.set_config(&config) }
+    }
+
+    /// Prevent any change of the configuration until the next reset.
+    pub fn lock(&mut self) {
+        self.raw().lock();
+    }
+
+    /// Only used while borrowing `self`.
+    fn raw(&self) -> super::Pin {
+        unsafe { Port::new(P::NAME).pin(N) }
+    }
+
+    /// Create the pin out of thin air, see `make_pin!`.
+    pub unsafe fn steal() -> Self {
+        Pin::new()
+    }
+
+    /// Consume the typed pin into the runtime pin.
+    pub fn erase(self) -> super::Pin {
+        unsafe { Port::new(P::NAME).pin(N) }
+    }
+
+    /// Put the pin in gpio mode and consume the pin into a gpio
+    pub fn make_gpio(self) -> Gpio<Uninitialized> {
+        self.erase().make_gpio()
+    }
+}
+
+impl<P: PortId, M, const N: usize> From<Pin<P, M, N>> for super::Pin {
+    fn from(pin: Pin<P, M, N>) -> super::Pin {
+        pin.erase()
+    }
+}
+
+impl<P: PortId, M, const N: usize> core::fmt::Debug for Pin<P, M, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "PT{}{}", (b'A' + P::NAME as u8) as char, N)
+    }
+}
+
+static mut TAKEN: bool = false;
+
+/// Generate the `Pins` struct of a board and the table used by `port::Pin::new`, from a list of
+/// `number => field: Port id`.
+macro_rules! pins {
+    ($($number:literal => $field:ident: $port:ident $id:literal,)+) => {
+        /// All the pins of the board, named after their number on the
+        /// [schematic representation of the teensy](https://github.com/irevoire/teensy/blob/master/doc/schematic.gif).
+        pub struct Pins {
+            $(pub $field: $crate::port::pins::Pin<
+                $crate::port::$port,
+                $crate::port::mux::Unconfigured,
+                $id,
+            >,)+
+        }
+
+        impl Pins {
+            /// Return the pins, even if they were already taken.
+            pub unsafe fn steal() -> Pins {
+                Pins {
+                    $($field: $crate::port::pins::Pin::steal(),)+
+                }
+            }
+        }
+
+        /// The port and the index in the port of a pin number.
+        pub fn pin_location(number: usize) -> Option<($crate::port::PortName, usize)> {
+            match number {
+                $($number => Some((
+                    <$crate::port::$port as $crate::port::PortId>::NAME,
+                    $id,
+                )),)+
+                _ => None,
+            }
+        }
+    };
+}
+pub(crate) use pins;
+
+pub use crate::board::Pins;
+
+impl Pins {
+    /// Return the pins the first time it is called, then `None`.
+    pub fn take() -> Option<Pins> {
+        crate::interrupts::free(|| unsafe {
+            if TAKEN {
+                None
+            } else {
+                TAKEN = true;
+                Some(Pins::steal())
+            }
+        })
+    }
+}
@@ -1,7 +1,34 @@
+use super::config::PinConfig;
 use super::pin::Pin;
+use bit_field::BitField;
+use core::time::Duration;
 use volatile::Volatile;
 
-#[derive(Clone, Copy)]
+/// Frequency of the LPO, in Hz
+const LPO_FREQUENCY: u64 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The filter width must be at most 31 clock cycles
+    InvalidFilterWidth(u8),
+    /// The debounce duration doesn't fit in 31 cycles of the LPO
+    InvalidDebounce,
+    /// A bus needs between 1 and 32 distinct pins of the same port
+    InvalidBus,
+    /// The configuration of the pin is locked until the next reset
+    Locked,
+}
+
+/// The clock of the digital filters of a port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterClock {
+    /// The bus clock, for glitches up to 31 bus cycles
+    Bus = 0,
+    /// The 1kHz low power oscillator, for glitches up to 31ms. Keeps running in stop modes
+    Lpo = 1,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum PortName {
     A,
     B,
@@ -20,10 +47,25 @@ pub struct Port {
     Bits 8-10 : MUX
     */
     pub pcr: [Volatile<u32>; 32],
+    /// 11.14.2 Global Pin Control Low Register (PORTx_GPCLR)
+    /// Bits 16-31: which pins between 0 and 15 are written
+    /// Bits 0-15: the value written in the lower half of their PCR
     gpclr: Volatile<u32>,
+    /// 11.14.3 Global Pin Control High Register (PORTx_GPCHR)
+    /// Same as GPCLR, for the pins 16 to 31
     gpchr: Volatile<u32>,
     _reserved: Volatile<[u8; 24]>,
+    /// 11.14.4 Interrupt Status Flag Register (PORTx_ISFR)
+    /// One bit per pin, write one to clear
     isfr: Volatile<u32>,
+    _reserved1: Volatile<[u8; 28]>,
+    /// 11.14.5 Digital Filter Enable Register (PORTx_DFER)
+    /// One bit per pin
+    dfer: Volatile<u32>,
+    /// 11.14.6 Digital Filter Clock Register (PORTx_DFCR)
+    dfcr: Volatile<u32>,
+    /// 11.14.7 Digital Filter Width Register (PORTx_DFWR)
+    dfwr: Volatile<u32>,
 }
 
 impl Port {
@@ -51,6 +93,105 @@ impl Port {
         }
     }
 
+    /// The pins of this port with a pending interrupt, one bit per pin.
+    pub fn interrupt_flags(&self) -> u32 {
+        self.isfr.read()
+    }
+
+    /// Clear the pending interrupts and return them, one bit per pin.
+    pub fn take_interrupt_flags(&mut self) -> u32 {
+        let flags = self.isfr.read();
+        self.isfr.write(flags);
+        flags
+    }
+
+    /// Apply the same configuration to all the pins in `mask`, one bit per pin, with a single
+    /// write per half of the port. The interrupt configuration of the pins is left untouched.
+    /// The locked pins are skipped, their digital filter included.
+    ///
+    /// Nothing checks that the pins are not owned by another piece of code.
+    /// ```rust
+    /// // an 8 bits bus on PTD0 to PTD7, the pins 2, 14, 7, 8, 6, 20, 21 and 5
+    /// let port = unsafe { port::Port::new(port::PortName::D) };
+    /// let config = port::PinConfig {
+    ///     mux: 1,
+    ///     high_drive_strength: true,
+    ///     ..Default::default()
+    /// };
+    /// port.configure_pins(0xFF, &config);
+    /// ```
+    pub fn configure_pins(&mut self, mask: u32, config: &PinConfig) {
+        let pcr = config.pcr_bits() as u32;
+        if mask & 0xFFFF != 0 {
+            self.gpclr.write((mask & 0xFFFF) << 16 | pcr);
+        }
+        if mask >> 16 != 0 {
+            self.gpchr.write(mask & 0xFFFF_0000 | pcr);
+        }
+
+        // GPCLR and GPCHR don't write the locked pins, DFER must skip them too
+        let locked = (0..32)
+            .filter(|p| self.pcr[*p].read().get_bit(15))
+            .fold(0, |locked, p| locked | 1 << p);
+        let mask = mask & !locked;
+        crate::interrupts::free(|| {
+            self.dfer.update(|dfer| {
+                if config.digital_filter {
+                    *dfer |= mask;
+                } else {
+                    *dfer &= !mask;
+                }
+            });
+        });
+    }
+
+    /// Enable the digital filter of the pin `p`. The filter clock and width are shared by all
+    /// the pins of the port.
+    pub fn enable_filter(&mut self, p: usize, enable: bool) {
+        assert!(p < 32);
+        crate::interrupts::free(|| {
+            self.dfer.update(|dfer| {
+                dfer.set_bit(p, enable);
+            });
+        });
+    }
+
+    /// The pins of this port with a digital filter enabled, one bit per pin.
+    pub fn filters(&self) -> u32 {
+        self.dfer.read()
+    }
+
+    /// Select the clock of the digital filters. Should only be changed while all the filters
+    /// of the port are disabled.
+    pub fn set_filter_clock(&mut self, clock: FilterClock) {
+        self.dfcr.write(clock as u32);
+    }
+
+    /// Glitches shorter than `width` cycles of the filter clock are ignored, zero disables the
+    /// filtering. Should only be changed while all the filters of the port are disabled.
+    pub fn set_filter_width(&mut self, width: u8) -> Result<(), Error> {
+        if width > 31 {
+            return Err(Error::InvalidFilterWidth(width));
+        }
+        self.dfwr.write(width as u32);
+        Ok(())
+    }
+
+    /// Configure the filter clock and width to ignore the changes shorter than `duration`. The
+    /// bus clock is used if possible, the LPO otherwise. The enabled filters are disabled while
+    /// the clock and width change, then enabled again.
+    pub fn set_debounce(&mut self, duration: Duration) -> Result<(), Error> {
+        let (clock, width) = filter_width(duration)?;
+        crate::interrupts::free(|| {
+            let filters = self.dfer.read();
+            self.dfer.write(0);
+            self.set_filter_clock(clock);
+            let result = self.set_filter_width(width);
+            self.dfer.write(filters);
+            result
+        })
+    }
+
     /// Consume the port into a pin
     /// The pin keep a reference to it's pcr field
     pub unsafe fn pin(&'static mut self, p: usize) -> Pin {
@@ -62,3 +203,27 @@ impl Port {
         }
     }
 }
+
+/// The filter clock and the smallest width covering `duration`, see `Port::set_debounce`.
+pub fn filter_width(duration: Duration) -> Result<(FilterClock, u8), Error> {
+    // `None` when the computation overflows, the duration is then far too long for the filter
+    let cycles = |frequency: u64| {
+        let nanos = duration.as_nanos();
+        if nanos > u64::MAX as u128 {
+            return None;
+        }
+        (nanos as u64)
+            .checked_mul(frequency)?
+            .checked_add(999_999_999)
+            .map(|n| n / 1_000_000_000)
+    };
+
+    match cycles(crate::mcg::F_BUS as u64) {
+        Some(bus) if bus <= 31 => return Ok((FilterClock::Bus, bus as u8)),
+        _ => {}
+    }
+    match cycles(LPO_FREQUENCY) {
+        Some(lpo) if lpo <= 31 => Ok((FilterClock::Lpo, lpo as u8)),
+        _ => Err(Error::InvalidDebounce),
+    }
+}
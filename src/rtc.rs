@@ -0,0 +1,322 @@
+//! # Real Time Clock (RTC)
+//! The RTC counts the seconds from the 32.768kHz oscillator (see `osc::RtcOsc`). It is powered
+//! by the VBAT pin, so with a coin cell it keeps the time across resets and power losses.
+//!
+//! The counter is a plain number of seconds, `DateTime` converts it from and to a calendar date
+//! using the unix epoch (1970-01-01 00:00:00).
+//! ```rust
+//! let (sim, rtc) = unsafe { (sim::Sim::new(), rtc::Rtc::new()) };
+//! sim.enable_clock(sim::Clock::Rtc);
+//! if !rtc.is_valid() {
+//!     // the time was lost, the oscillator must be restarted
+//!     rtc.osc().enable(12).unwrap();
+//!     sleep::sleep_ms(1000);
+//!     rtc.set_datetime(&rtc::DateTime::new(2019, 1, 1, 12, 0, 0).unwrap()).unwrap();
+//! }
+//! let now = rtc.datetime();
+//! ```
+
+use crate::osc::RtcOsc;
+use bit_field::BitField;
+use volatile::Volatile;
+
+const RTC_ALARM_VECTOR: usize = crate::board::vectors::RTC_ALARM;
+const RTC_SECONDS_VECTOR: usize = crate::board::vectors::RTC_SECONDS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The date is not a valid date between 1970 and 2106-02-07
+    InvalidDate,
+    /// The compensation interval must be between 1 and 256 seconds
+    InvalidInterval,
+}
+
+static mut ALARM_HANDLER: Option<fn()> = None;
+static mut SECONDS_HANDLER: Option<fn()> = None;
+
+#[repr(C, packed)]
+pub struct Rtc {
+    tsr: Volatile<u32>,
+    tpr: Volatile<u32>,
+    tar: Volatile<u32>,
+    tcr: Volatile<u32>,
+    osc: RtcOsc,
+    sr: Volatile<u32>,
+    lr: Volatile<u32>,
+    ier: Volatile<u32>,
+}
+
+impl Rtc {
+    /// The RTC clock gate (`sim::Clock::Rtc`) must be enabled before using it.
+    pub unsafe fn new() -> &'static mut Rtc {
+        &mut *(0x4003_D000 as *mut Rtc)
+    }
+
+    /// The 32.768kHz oscillator, to set its load capacitance or start it.
+    pub fn osc(&mut self) -> &mut RtcOsc {
+        &mut self.osc
+    }
+
+    /// False after a power loss (TIF) or an overflow of the counter (TOF). The counter is
+    /// stopped and reads zero until the time is set again.
+    pub fn is_valid(&self) -> bool {
+        let sr = self.sr.read();
+        !sr.get_bit(0) && !sr.get_bit(1)
+    }
+
+    /// TCE: the seconds counter is running.
+    pub fn is_running(&self) -> bool {
+        self.sr.read().get_bit(4)
+    }
+
+    pub fn start(&mut self) {
+        self.sr.update(|sr| {
+            sr.set_bit(4, true);
+        });
+    }
+
+    pub fn stop(&mut self) {
+        self.sr.update(|sr| {
+            sr.set_bit(4, false);
+        });
+    }
+
+    /// The number of seconds since the epoch.
+    pub fn seconds(&self) -> u32 {
+        self.tsr.read()
+    }
+
+    /// Set the number of seconds since the epoch and start the counter. This clears the invalid
+    /// and overflow flags.
+    pub fn set_seconds(&mut self, seconds: u32) {
+        self.stop();
+        self.tpr.write(0);
+        self.tsr.write(seconds);
+        self.start();
+    }
+
+    pub fn datetime(&self) -> DateTime {
+        DateTime::from_seconds(self.seconds())
+    }
+
+    pub fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Error> {
+        self.set_seconds(datetime.to_seconds()?);
+        Ok(())
+    }
+
+    /// Set the alarm flag when the counter reaches `seconds`. This clears the alarm flag.
+    pub fn set_alarm(&mut self, seconds: u32) {
+        // The flag is set when the counter increments while being equal to TAR
+        self.tar.write(seconds.wrapping_sub(1));
+    }
+
+    /// TAF: the alarm time was reached.
+    pub fn alarm_expired(&self) -> bool {
+        self.sr.read().get_bit(2)
+    }
+
+    /// Clear the alarm flag, without changing the alarm time.
+    pub fn clear_alarm(&mut self) {
+        // writing TAR clears the flag
+        let tar = self.tar.read();
+        self.tar.write(tar);
+    }
+
+    /// Interrupt when the alarm time is reached, the handler registered with
+    /// `set_alarm_handler` is called.
+    pub fn enable_alarm_interrupt(&mut self) {
+        self.ier.update(|ier| {
+            // the invalid and overflow interrupts share the vector, and are enabled on reset
+            ier.set_bit(0, false);
+            ier.set_bit(1, false);
+            ier.set_bit(2, true);
+        });
+        crate::interrupts::enable(RTC_ALARM_VECTOR);
+    }
+
+    pub fn disable_alarm_interrupt(&mut self) {
+        self.ier.update(|ier| {
+            ier.set_bit(2, false);
+        });
+        crate::interrupts::disable(RTC_ALARM_VECTOR);
+    }
+
+    /// Interrupt every second, the handler registered with `set_seconds_handler` is called.
+    pub fn enable_seconds_interrupt(&mut self) {
+        self.ier.update(|ier| {
+            ier.set_bit(4, true);
+        });
+        crate::interrupts::enable(RTC_SECONDS_VECTOR);
+    }
+
+    pub fn disable_seconds_interrupt(&mut self) {
+        self.ier.update(|ier| {
+            ier.set_bit(4, false);
+        });
+        crate::interrupts::disable(RTC_SECONDS_VECTOR);
+    }
+
+    /// Correct the crystal frequency error. Once every `interval` seconds (1 to 256), the
+    /// second lasts `32768 - cycles` oscillator cycles instead of 32768. A positive value makes
+    /// the clock faster: a crystal running 10ppm slow is fixed with 82 cycles every 250
+    /// seconds.
+    pub fn set_compensation(&mut self, cycles: i8, interval: u16) -> Result<(), Error> {
+        if interval == 0 || interval > 256 {
+            return Err(Error::InvalidInterval);
+        }
+
+        self.tcr.update(|tcr| {
+            tcr.set_bits(0..8, cycles as u8 as u32);
+            tcr.set_bits(8..16, interval as u32 - 1);
+        });
+        Ok(())
+    }
+
+    /// The compensation as `(cycles, interval)`, see `set_compensation`.
+    pub fn compensation(&self) -> (i8, u16) {
+        let tcr = self.tcr.read();
+        (tcr.get_bits(0..8) as u8 as i8, tcr.get_bits(8..16) as u16 + 1)
+    }
+}
+
+/// A date and time in UTC, from 1970 to 2106.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: u16,
+    /// From 1 to 12
+    pub month: u8,
+    /// From 1 to 31
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<DateTime, Error> {
+        let datetime = DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        };
+        datetime.to_seconds()?;
+        Ok(datetime)
+    }
+
+    /// The date of a number of seconds since the epoch.
+    pub fn from_seconds(seconds: u32) -> DateTime {
+        let days = seconds / 86400;
+        let time = seconds % 86400;
+
+        // Days to civil date, with years starting on March 1st so that the leap day is the
+        // last day of the year. http://howardhinnant.github.io/date_algorithms.html
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        DateTime {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (time / 3600) as u8,
+            minute: (time / 60 % 60) as u8,
+            second: (time % 60) as u8,
+        }
+    }
+
+    /// The number of seconds since the epoch.
+    pub fn to_seconds(&self) -> Result<u32, Error> {
+        if self.year < 1970
+            || self.month == 0
+            || self.month > 12
+            || self.day == 0
+            || self.day > days_in_month(self.year, self.month)
+            || self.hour > 23
+            || self.minute > 59
+            || self.second > 59
+        {
+            return Err(Error::InvalidDate);
+        }
+
+        // Civil date to days, see `from_seconds`
+        let (year, month) = (self.year as u64, self.month as u64);
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year / 400;
+        let year_of_era = year % 400;
+        let month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * month + 2) / 5 + self.day as u64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        let seconds = days * 86400
+            + self.hour as u64 * 3600
+            + self.minute as u64 * 60
+            + self.second as u64;
+        if seconds > u32::MAX as u64 {
+            return Err(Error::InvalidDate);
+        }
+        Ok(seconds as u32)
+    }
+
+    /// From 0 (monday) to 6 (sunday).
+    pub fn weekday(&self) -> u8 {
+        // the epoch was a thursday
+        ((self.to_seconds().unwrap_or(0) / 86400 + 3) % 7) as u8
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Register the function called when the alarm time is reached. The alarm flag is cleared for
+/// you.
+pub fn set_alarm_handler(handler: fn()) {
+    crate::interrupts::free(|| unsafe {
+        ALARM_HANDLER = Some(handler);
+    });
+}
+
+/// Register the function called every second.
+pub fn set_seconds_handler(handler: fn()) {
+    crate::interrupts::free(|| unsafe {
+        SECONDS_HANDLER = Some(handler);
+    });
+}
+
+/// Called from the RTC alarm interrupt vector.
+pub(crate) unsafe fn on_alarm() {
+    Rtc::new().clear_alarm();
+    if let Some(handler) = ALARM_HANDLER {
+        handler();
+    }
+}
+
+/// Called from the RTC seconds interrupt vector.
+pub(crate) unsafe fn on_seconds() {
+    if let Some(handler) = SECONDS_HANDLER {
+        handler();
+    }
+}
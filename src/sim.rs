@@ -13,6 +13,22 @@ pub enum Clock {
     PortC,
     PortD,
     PortE,
+    Rtc,
+    Ewm,
+    Pit,
+    Lptmr,
+    Tsi,
+}
+
+/// Source of the 32kHz clock (ERCLK32K) used by the LPTMR and in low power modes.
+#[derive(Clone, Copy)]
+pub enum Erclk32kSource {
+    /// The main oscillator divided down to 32kHz (OSC32KCLK)
+    System = 0,
+    /// The 32.768kHz oscillator of the RTC
+    Rtc = 2,
+    /// The 1kHz low power oscillator
+    Lpo = 3,
 }
 
 /// doc/teensy_3.2.pdf - Page 236
@@ -82,10 +98,42 @@ impl Sim {
                         scgc5.set_bit(13, true);
                     });
                 }
+                Clock::Ewm => {
+                    self.scgc4.update(|scgc4| {
+                        scgc4.set_bit(1, true);
+                    });
+                }
+                Clock::Lptmr => {
+                    self.scgc5.update(|scgc5| {
+                        scgc5.set_bit(0, true);
+                    });
+                }
+                Clock::Pit => {
+                    self.scgc6.update(|scgc6| {
+                        scgc6.set_bit(23, true);
+                    });
+                }
+                Clock::Rtc => {
+                    self.scgc6.update(|scgc6| {
+                        scgc6.set_bit(29, true);
+                    });
+                }
+                Clock::Tsi => {
+                    self.scgc5.update(|scgc5| {
+                        scgc5.set_bit(5, true);
+                    });
+                }
             }
         }
     }
 
+    /// OSC32KSEL: select the source of ERCLK32K
+    pub fn set_erclk32k_source(&mut self, source: Erclk32kSource) {
+        self.sopt1.update(|sopt1| {
+            sopt1.set_bits(18..20, source as u32);
+        });
+    }
+
     /// 12.2.15 System Clock Divider Register 1 found at doc/teensy_3.2.pdf - page 259
     pub fn set_dividers(&mut self, core: u32, bus: u32, flash: u32) {
         let mut clkdiv: u32 = 0;
@@ -1,4 +1,4 @@
-use core::arch::arm::__nop;
+use embedded_hal::blocking::delay::{DelayMs, DelayUs};
 
 /// This function come directly from the [cortex_m
 /// crate](https://docs.rs/cortex-m/0.6.2/cortex_m/asm/fn.delay.html)
@@ -18,44 +18,142 @@ pub fn delay(n: u64) {
     }
 }
 
-/// This implementation will be more imprecise with lower CPU frequencies and
-/// not properly work at all with anything below 5 MHz
+/// Busy wait on the cycle counter (see the `cycles` module), which is enabled if needed.
+/// Unlike a loop of `nop` this is not affected by the CPU frequency or by the interrupts, as long
+/// as `mcg::F_CPU` is right.
 #[inline]
 pub fn sleep_us(microseconds: u32) {
-    (0..microseconds).for_each(|_| {
-        let mut inner = crate::mcg::F_CPU / 5_000_000;
-
-        // This loop should take 5 cycles
-        while inner != 0 {
-            inner -= 1;
-            unsafe {
-                __nop();
+    wait_cycles(microseconds as u64 * crate::mcg::F_CPU as u64 / 1_000_000, false);
+}
+
+/// See `sleep_us`.
+pub fn sleep_ms(milliseconds: u32) {
+    wait_cycles(milliseconds as u64 * crate::mcg::F_CPU as u64 / 1_000, false);
+}
+
+/// Wait `cycles` core cycles. If `sleep` is set and the SysTick is running, the core sleeps
+/// until the next tick when there is enough time left.
+fn wait_cycles(cycles: u64, sleep: bool) {
+    if !crate::cycles::is_enabled() {
+        crate::cycles::enable();
+    }
+    let sleep = sleep && crate::time::is_running();
+    let tick = unsafe { crate::time::SysTick::new().cycles_per_tick() };
+
+    // the counter wraps every 2^32 cycles so we wait in smaller chunks
+    let mut remaining = cycles;
+    while remaining > 0 {
+        let chunk = remaining.min(u32::MAX as u64 / 2) as u32;
+        let start = crate::cycles::now();
+        loop {
+            let elapsed = crate::cycles::now().wrapping_sub(start);
+            if elapsed >= chunk {
+                break;
+            }
+            if sleep && chunk - elapsed > tick {
+                wait_for_interrupt();
             }
         }
-    });
+        remaining -= chunk as u64;
+    }
 }
 
-/// For milliseconds, this loop approach is not too far off reality as long
-/// as it runs uninterrupted.
-pub fn sleep_ms(milliseconds: u32) {
-    (0..milliseconds).for_each(|_| {
-        let mut inner = crate::mcg::F_CPU / 10_000;
-
-        // This loop should take 10 cycles:
-        //  inner -= 1     sub: 1 cycle
-        //  inner != 0     beq: 1 cycle if not taken
-        //  6*nop          nop: 1 cycle
-        //  endwhile       b:   1 + (1-3) cycles
-        while inner != 0 {
-            inner -= 1;
-            unsafe {
-                __nop(); // 1
-                __nop(); // 2
-                __nop(); // 3
-                __nop(); // 4
-                __nop(); // 5
-                __nop(); // 6
+/// Stop the core until the next interrupt.
+#[inline]
+pub fn wait_for_interrupt() {
+    unsafe {
+        asm!("wfi");
+    }
+}
+
+/// A delay based on the `time` module. It is precise to a few microseconds and the core sleeps
+/// between the ticks instead of spinning.
+/// The SysTick must be started, which is done by the default `init`.
+pub struct SysTickDelay {
+    _private: (),
+}
+
+impl SysTickDelay {
+    /// Return `None` if the SysTick is not started, see `time::start`.
+    pub fn new() -> Option<Self> {
+        if crate::time::is_running() {
+            Some(SysTickDelay { _private: () })
+        } else {
+            None
+        }
+    }
+
+    pub fn delay_us(&mut self, microseconds: u64) {
+        let end = crate::time::micros() + microseconds;
+        loop {
+            let now = crate::time::micros();
+            if now >= end {
+                break;
+            }
+            // we can sleep only if the next tick arrive before the end of the delay
+            if end - now > 1_000 {
+                wait_for_interrupt();
             }
         }
-    });
+    }
+}
+
+impl<T: Into<u32>> DelayMs<T> for SysTickDelay {
+    type Error = !;
+
+    fn try_delay_ms(&mut self, ms: T) -> Result<(), Self::Error> {
+        self.delay_us(ms.into() as u64 * 1_000);
+        Ok(())
+    }
+}
+
+impl<T: Into<u32>> DelayUs<T> for SysTickDelay {
+    type Error = !;
+
+    fn try_delay_us(&mut self, us: T) -> Result<(), Self::Error> {
+        self.delay_us(us.into() as u64);
+        Ok(())
+    }
+}
+
+/// A delay counting the core cycles with the DWT cycle counter (see the `cycles` module). It is
+/// precise to a few cycles and does not need any interrupt. If the SysTick is running the core
+/// sleeps between the ticks during long delays.
+pub struct CycleDelay {
+    core_clock: u32,
+}
+
+impl CycleDelay {
+    /// Enable the cycle counter. `core_clock` is the frequency of the core in Hz, usually
+    /// `mcg::F_CPU`.
+    pub fn new(core_clock: u32) -> Self {
+        crate::cycles::enable();
+        CycleDelay { core_clock }
+    }
+
+    pub fn delay_cycles(&mut self, cycles: u64) {
+        wait_cycles(cycles, true);
+    }
+
+    pub fn delay_us(&mut self, microseconds: u64) {
+        self.delay_cycles(microseconds * self.core_clock as u64 / 1_000_000);
+    }
+}
+
+impl<T: Into<u32>> DelayMs<T> for CycleDelay {
+    type Error = !;
+
+    fn try_delay_ms(&mut self, ms: T) -> Result<(), Self::Error> {
+        self.delay_us(ms.into() as u64 * 1_000);
+        Ok(())
+    }
+}
+
+impl<T: Into<u32>> DelayUs<T> for CycleDelay {
+    type Error = !;
+
+    fn try_delay_us(&mut self, us: T) -> Result<(), Self::Error> {
+        self.delay_us(us.into() as u64);
+        Ok(())
+    }
 }
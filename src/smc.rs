@@ -0,0 +1,176 @@
+//! # System Mode Controller (SMC)
+//! The SMC puts the chip in its low power modes. From the most to the least power hungry:
+//! - RUN: the normal mode
+//! - WAIT: the core is stopped until an interrupt, the peripherals keep running
+//! - STOP: all the clocks are stopped, any interrupt wakes the chip up
+//! - VLPR / VLPW / VLPS: the very low power RUN, WAIT and STOP. The chip must run at 4MHz or
+//!   less (core and bus at 4MHz, flash at 1MHz, MCG in BLPI or BLPE mode) before entering VLPR
+//! - LLS: only the RTC, LPTMR, comparators and TSI run, the chip is woken up by the LLWU (see
+//!   the `llwu` module, teensy 3.0 to 3.2 only) and the execution continues after the call
+//! - VLLS3 / 2 / 1 / 0: same as LLS but the RAM is partially or totally powered down. The chip
+//!   is woken up by the LLWU **through a reset**, call `release_pins` once you reconfigured the
+//!   pins after the wakeup
+//!
+//! The very low power and low leakage modes must be allowed once after each reset with `allow`.
+//! ```rust
+//! let smc = unsafe { smc::Smc::new() };
+//! smc.allow(true, true, true);
+//! // configure a wakeup source with the llwu module then
+//! smc.stop(smc::StopMode::Lls).unwrap();
+//! ```
+
+use bit_field::BitField;
+use volatile::Volatile;
+
+/// System Control Register of the cortex-m4, holds the SLEEPDEEP bit.
+const SCB_SCR: u32 = 0xE000_ED10;
+/// Regulator Status And Control register of the Power Management Controller.
+const PMC_REGSC: u32 = 0x4007_D002;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The mode was not allowed with `allow` since the last reset
+    NotAllowed,
+    /// STOPA: the stop mode entry was aborted by an interrupt
+    Aborted,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StopMode {
+    Stop,
+    Vlps,
+    Lls,
+    Vlls0,
+    Vlls1,
+    Vlls2,
+    Vlls3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerMode {
+    Run,
+    Stop,
+    Vlpr,
+    Vlpw,
+    Vlps,
+    Lls,
+    Vlls,
+}
+
+#[repr(C, packed)]
+pub struct Smc {
+    pmprot: Volatile<u8>,
+    pmctrl: Volatile<u8>,
+    vllsctrl: Volatile<u8>,
+    pmstat: Volatile<u8>,
+}
+
+impl Smc {
+    pub unsafe fn new() -> &'static mut Smc {
+        &mut *(0x4007_E000 as *mut Smc)
+    }
+
+    /// Allow the very low power (VLPR, VLPW, VLPS), low leakage (LLS) and very low leakage
+    /// (VLLSx) modes. **This register can only be written once after a reset.**
+    pub fn allow(&mut self, very_low_power: bool, low_leakage: bool, very_low_leakage: bool) {
+        let mut pmprot: u8 = 0;
+        pmprot.set_bit(5, very_low_power);
+        pmprot.set_bit(3, low_leakage);
+        pmprot.set_bit(1, very_low_leakage);
+        self.pmprot.write(pmprot);
+    }
+
+    /// The current power mode.
+    pub fn mode(&self) -> PowerMode {
+        match self.pmstat.read() {
+            0x02 => PowerMode::Stop,
+            0x04 => PowerMode::Vlpr,
+            0x08 => PowerMode::Vlpw,
+            0x10 => PowerMode::Vlps,
+            0x20 => PowerMode::Lls,
+            0x40 => PowerMode::Vlls,
+            _ => PowerMode::Run,
+        }
+    }
+
+    /// Enter WAIT, or VLPW when running in VLPR, until the next interrupt. The default `init`
+    /// starts the SysTick which wakes the core up every millisecond.
+    pub fn wait(&mut self) {
+        set_sleep_deep(false);
+        crate::sleep::wait_for_interrupt();
+    }
+
+    /// Enter a stop mode until the next wakeup event. In the VLLS modes this function never
+    /// returns since the chip wakes up through a reset.
+    pub fn stop(&mut self, mode: StopMode) -> Result<(), Error> {
+        let pmprot = self.pmprot.read();
+        let (stopm, vllsm, allowed) = match mode {
+            StopMode::Stop => (0b000, 0, true),
+            StopMode::Vlps => (0b010, 0, pmprot.get_bit(5)),
+            StopMode::Lls => (0b011, 0, pmprot.get_bit(3)),
+            StopMode::Vlls0 => (0b100, 0, pmprot.get_bit(1)),
+            StopMode::Vlls1 => (0b100, 1, pmprot.get_bit(1)),
+            StopMode::Vlls2 => (0b100, 2, pmprot.get_bit(1)),
+            StopMode::Vlls3 => (0b100, 3, pmprot.get_bit(1)),
+        };
+        if !allowed {
+            return Err(Error::NotAllowed);
+        }
+
+        self.vllsctrl.update(|vllsctrl| {
+            vllsctrl.set_bits(0..3, vllsm);
+        });
+        self.pmctrl.update(|pmctrl| {
+            pmctrl.set_bits(0..3, stopm);
+        });
+        // read back the register to be sure the write is done before the wfi
+        let _ = self.pmctrl.read();
+
+        set_sleep_deep(true);
+        crate::sleep::wait_for_interrupt();
+        set_sleep_deep(false);
+
+        if self.pmctrl.read().get_bit(3) {
+            return Err(Error::Aborted);
+        }
+        Ok(())
+    }
+
+    /// Enter the very low power run mode, the clocks must already be slowed down.
+    pub fn enter_vlpr(&mut self) -> Result<(), Error> {
+        if !self.pmprot.read().get_bit(5) {
+            return Err(Error::NotAllowed);
+        }
+        self.pmctrl.update(|pmctrl| {
+            pmctrl.set_bits(5..7, 0b10);
+        });
+        while self.mode() != PowerMode::Vlpr {}
+        Ok(())
+    }
+
+    /// Go back to the normal run mode, the clocks can be raised once this returns.
+    pub fn exit_vlpr(&mut self) {
+        self.pmctrl.update(|pmctrl| {
+            pmctrl.set_bits(5..7, 0b00);
+        });
+        while self.mode() != PowerMode::Run {}
+    }
+}
+
+fn set_sleep_deep(deep: bool) {
+    unsafe {
+        let mut scr = core::ptr::read_volatile(SCB_SCR as *const u32);
+        scr.set_bit(2, deep);
+        core::ptr::write_volatile(SCB_SCR as *mut u32, scr);
+    }
+}
+
+/// After a wakeup from VLLS the pins keep their state until this is called. Configure the pins
+/// first, then release them.
+pub fn release_pins() {
+    unsafe {
+        // ACKISO, write one to clear
+        let regsc = core::ptr::read_volatile(PMC_REGSC as *const u8);
+        core::ptr::write_volatile(PMC_REGSC as *mut u8, regsc | 1 << 3);
+    }
+}
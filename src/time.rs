@@ -0,0 +1,194 @@
+//! # Time
+//! A monotonic clock driven by the SysTick timer of the cortex-m4. The SysTick interrupts the
+//! core every millisecond to maintain a 64 bits millisecond counter, and the current value of
+//! the SysTick counter gives the microseconds.
+//!
+//! The default `init` of this crate starts it, if you use the `manual_init` feature you need to
+//! call `time::start` yourself.
+//! ```rust
+//! let start = time::Instant::now();
+//! do_something();
+//! if start.elapsed() > time::Duration::from_millis(10) {
+//!     // timeout
+//! }
+//! ```
+
+use bit_field::BitField;
+use core::ops::{Add, AddAssign, Sub};
+use volatile::Volatile;
+
+pub use core::time::Duration;
+
+/// Interrupt Control and State Register, holds the pending flag of the SysTick exception.
+const SCB_ICSR: u32 = 0xE000_ED04;
+
+/// Milliseconds elapsed since `start`.
+static mut TICKS: u64 = 0;
+
+#[repr(C, packed)]
+pub struct SysTick {
+    csr: Volatile<u32>,
+    rvr: Volatile<u32>,
+    cvr: Volatile<u32>,
+    calib: Volatile<u32>,
+}
+
+impl SysTick {
+    pub unsafe fn new() -> &'static mut SysTick {
+        &mut *(0xE000_E010 as *mut SysTick)
+    }
+
+    /// Number of core cycles between two ticks.
+    pub fn cycles_per_tick(&self) -> u32 {
+        self.rvr.read() + 1
+    }
+
+    /// Number of core cycles elapsed since the last tick.
+    pub fn cycles_since_tick(&self) -> u32 {
+        self.rvr.read() - self.cvr.read()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.csr.read().get_bit(0)
+    }
+}
+
+/// Configure the SysTick to interrupt every millisecond. `core_clock` is the frequency of the
+/// core in Hz, usually `mcg::F_CPU`.
+pub fn start(core_clock: u32) {
+    let systick = unsafe { SysTick::new() };
+    crate::interrupts::free(|| {
+        systick.csr.write(0);
+        systick.rvr.write(core_clock / 1_000 - 1);
+        systick.cvr.write(0);
+        unsafe {
+            TICKS = 0;
+        }
+        let mut csr: u32 = 0;
+        csr.set_bit(0, true); // enable
+        csr.set_bit(1, true); // interrupt
+        csr.set_bit(2, true); // clocked by the core
+        systick.csr.write(csr);
+    });
+}
+
+/// Return true if `start` was called.
+pub fn is_running() -> bool {
+    unsafe { SysTick::new().is_enabled() }
+}
+
+/// Read the milliseconds and the cycles elapsed since the last millisecond atomically.
+fn now() -> (u64, u32) {
+    let systick = unsafe { SysTick::new() };
+    crate::interrupts::free(|| unsafe {
+        let mut ticks = TICKS;
+        let mut cycles = systick.cycles_since_tick();
+        // The counter wrapped while the interrupts were masked, the tick is not counted yet.
+        // Read the counter again to be sure it is after the wrap.
+        let icsr = core::ptr::read_volatile(SCB_ICSR as *const u32);
+        if icsr.get_bit(26) {
+            ticks += 1;
+            cycles = systick.cycles_since_tick();
+        }
+        (ticks, cycles)
+    })
+}
+
+/// Milliseconds elapsed since `start`.
+pub fn millis() -> u64 {
+    now().0
+}
+
+/// Microseconds elapsed since `start`.
+pub fn micros() -> u64 {
+    let (ticks, cycles) = now();
+    let cycles_per_tick = unsafe { SysTick::new().cycles_per_tick() };
+    ticks * 1_000 + cycles as u64 * 1_000 / cycles_per_tick as u64
+}
+
+/// A point in time, with a microsecond resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instant {
+    micros: u64,
+}
+
+impl Instant {
+    pub fn now() -> Instant {
+        Instant { micros: micros() }
+    }
+
+    /// Time elapsed since this instant.
+    pub fn elapsed(&self) -> Duration {
+        Instant::now() - *self
+    }
+
+    /// Time elapsed between `earlier` and this instant, zero if `earlier` is later.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        Duration::from_micros(self.micros.saturating_sub(earlier.micros))
+    }
+
+    /// Microseconds elapsed between `start` and this instant.
+    pub fn as_micros(&self) -> u64 {
+        self.micros
+    }
+
+    /// `None` if the result overflows.
+    pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        let micros = self.micros.checked_add(micros_of(duration)?)?;
+        Some(Instant { micros })
+    }
+
+    /// `None` if the result would be before `start`.
+    pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+        let micros = self.micros.checked_sub(micros_of(duration)?)?;
+        Some(Instant { micros })
+    }
+}
+
+fn micros_of(duration: Duration) -> Option<u64> {
+    let micros = duration.as_micros();
+    if micros > u64::MAX as u128 {
+        None
+    } else {
+        Some(micros as u64)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, other: Duration) -> Instant {
+        Instant {
+            micros: self.micros + other.as_micros() as u64,
+        }
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, other: Duration) {
+        *self = *self + other;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    /// Saturate at `start`, see `checked_sub`.
+    fn sub(self, other: Duration) -> Instant {
+        self.checked_sub(other).unwrap_or(Instant { micros: 0 })
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, other: Instant) -> Duration {
+        self.duration_since(other)
+    }
+}
+
+/// Called from the SysTick exception vector.
+pub(crate) unsafe fn on_tick() {
+    TICKS += 1;
+    crate::timers::on_tick(TICKS);
+}
@@ -0,0 +1,148 @@
+//! # Software timers
+//! Schedule one-shot and periodic callbacks with a millisecond resolution, without a busy loop
+//! per timer. The timers are kept in a list sorted by deadline, in static storage, and the
+//! SysTick (see the `time` module) calls the expired ones every millisecond.
+//!
+//! The callbacks run in the SysTick exception, so they must be short and can't block. They can
+//! schedule or cancel timers, including their own.
+//! ```rust
+//! timers::every(time::Duration::from_millis(500), || { /* toggle a led */ }).unwrap();
+//! let timeout = timers::after(time::Duration::from_secs(2), || { /* give up */ }).unwrap();
+//! // later, the answer arrived in time
+//! timers::cancel(timeout);
+//! ```
+
+use crate::time::Duration;
+
+/// Maximum number of timers scheduled at the same time.
+pub const MAX_TIMERS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// All the `MAX_TIMERS` slots are already used
+    TooManyTimers,
+    /// A periodic timer needs a period of at least one millisecond
+    InvalidPeriod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerId(u32);
+
+#[derive(Clone, Copy)]
+struct Timer {
+    id: u32,
+    /// In milliseconds since `time::start`
+    deadline: u64,
+    /// Zero for a one-shot timer
+    period: u64,
+    callback: fn(),
+}
+
+/// Sorted by deadline, the free slots are at the end.
+static mut QUEUE: [Option<Timer>; MAX_TIMERS] = [None; MAX_TIMERS];
+static mut NEXT_ID: u32 = 0;
+
+/// Call `callback` once, `delay` from now. With the millisecond resolution, it is called
+/// between `delay - 1ms` and `delay` later.
+pub fn after(delay: Duration, callback: fn()) -> Result<TimerId, Error> {
+    schedule(delay.as_millis() as u64, 0, callback)
+}
+
+/// Call `callback` every `period`, starting `period` from now. The calls don't drift: a late
+/// call doesn't delay the next ones.
+pub fn every(period: Duration, callback: fn()) -> Result<TimerId, Error> {
+    let period = period.as_millis() as u64;
+    if period == 0 {
+        return Err(Error::InvalidPeriod);
+    }
+    schedule(period, period, callback)
+}
+
+fn schedule(delay: u64, period: u64, callback: fn()) -> Result<TimerId, Error> {
+    crate::interrupts::free(|| unsafe {
+        let id = NEXT_ID;
+        insert(Timer {
+            id,
+            deadline: crate::time::millis() + delay,
+            period,
+            callback,
+        })?;
+        NEXT_ID = NEXT_ID.wrapping_add(1);
+        Ok(TimerId(id))
+    })
+}
+
+/// Cancel a timer. Return false if it already expired, or was already cancelled.
+pub fn cancel(timer: TimerId) -> bool {
+    crate::interrupts::free(|| unsafe {
+        match position(timer) {
+            Some(n) => {
+                remove(n);
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Return true until a one-shot timer expires, or a timer is cancelled.
+pub fn is_scheduled(timer: TimerId) -> bool {
+    crate::interrupts::free(|| unsafe { position(timer).is_some() })
+}
+
+/// Must be called with the interrupts masked.
+unsafe fn position(timer: TimerId) -> Option<usize> {
+    QUEUE
+        .iter()
+        .position(|slot| matches!(slot, Some(t) if t.id == timer.0))
+}
+
+/// Must be called with the interrupts masked.
+unsafe fn insert(timer: Timer) -> Result<(), Error> {
+    if QUEUE[MAX_TIMERS - 1].is_some() {
+        return Err(Error::TooManyTimers);
+    }
+
+    // after the timers with the same deadline, to keep the scheduling order
+    let n = QUEUE
+        .iter()
+        .position(|slot| match slot {
+            Some(t) => t.deadline > timer.deadline,
+            None => true,
+        })
+        .unwrap_or(MAX_TIMERS - 1);
+    QUEUE[n..].rotate_right(1);
+    QUEUE[n] = Some(timer);
+    Ok(())
+}
+
+/// Must be called with the interrupts masked.
+unsafe fn remove(n: usize) {
+    QUEUE[n] = None;
+    QUEUE[n..].rotate_left(1);
+}
+
+/// Called by the SysTick exception with the current number of milliseconds.
+pub(crate) unsafe fn on_tick(now: u64) {
+    loop {
+        let expired = crate::interrupts::free(|| match QUEUE[0] {
+            Some(timer) if timer.deadline <= now => {
+                remove(0);
+                if timer.period != 0 {
+                    // a slot was just freed, this can't fail
+                    let _ = insert(Timer {
+                        deadline: timer.deadline + timer.period,
+                        ..timer
+                    });
+                }
+                Some(timer)
+            }
+            _ => None,
+        });
+
+        match expired {
+            Some(timer) => (timer.callback)(),
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! # Touch Sensing Input (TSI)
+//! The TSI measures the capacitance of an electrode: it charges and discharges the electrode
+//! with a constant current and counts the cycles of a reference oscillator during a number of
+//! electrode oscillations. A finger on the electrode increases its capacitance, and thus the
+//! count.
+//!
+//! 16 channels are wired to the pins 0, 1, 15, 16, 17, 18, 19, 22, 23, 25, 32 and 33 of the
+//! teensy 3.0, 3.1 and 3.2. The teensy 3.5 has no TSI, and the one of the 3.6 is different.
+//!
+//! The quick way, like `touchRead` on arduino:
+//! ```rust
+//! let mut pin = pins.p23.erase();
+//! let count = tsi::touch_read(&mut pin).unwrap();
+//! ```
+//!
+//! Scanning continuously and interrupting when the pin 23 is touched:
+//! ```rust
+//! let (sim, tsi) = unsafe { (sim::Sim::new(), tsi::Tsi::new()) };
+//! sim.enable_clock(sim::Clock::Tsi);
+//! tsi.configure(&tsi::Config::default()).unwrap();
+//! let channel = tsi.set_pin(pins.p23).unwrap();
+//! tsi.set_threshold(channel, 0, 2000);
+//! tsi::set_handler(|| { /* touched, or released */ });
+//! tsi.enable_interrupt();
+//! tsi.start_continuous(&[channel]);
+//! ```
+
+use crate::port::{Pin, PortName};
+use bit_field::BitField;
+use volatile::Volatile;
+
+const TSI_VECTOR: usize = crate::board::vectors::TSI;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// This pin has no TSI channel
+    InvalidPin,
+    /// The charge currents are `2µA * (n + 1)` with n between 0 and 15
+    InvalidCurrent(u8),
+    /// The electrode oscillator is divided by `2^n` with n between 0 and 7
+    InvalidPrescaler(u8),
+    /// Each electrode is scanned between 1 and 32 times
+    InvalidScanCount(u8),
+    /// A continuous scan is running, read the counts with `count`
+    Busy,
+}
+
+/// A TSI channel, obtained from its pin with `Tsi::set_pin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Channel(u8);
+
+impl Channel {
+    /// The channel of a pin, if it has one.
+    pub fn of(pin: &Pin) -> Option<Channel> {
+        let channel = match (pin.portname, pin.id) {
+            (PortName::B, 0) => 0,
+            (PortName::A, 0) => 1,
+            (PortName::A, 1) => 2,
+            (PortName::A, 2) => 3,
+            (PortName::A, 3) => 4,
+            (PortName::A, 4) => 5,
+            (PortName::B, 1) => 6,
+            (PortName::B, 2) => 7,
+            (PortName::B, 3) => 8,
+            (PortName::B, 16) => 9,
+            (PortName::B, 17) => 10,
+            (PortName::B, 18) => 11,
+            (PortName::B, 19) => 12,
+            (PortName::C, 0) => 13,
+            (PortName::C, 1) => 14,
+            (PortName::C, 2) => 15,
+            _ => return None,
+        };
+        Some(Channel(channel))
+    }
+
+    pub fn number(&self) -> u8 {
+        self.0
+    }
+}
+
+/// The measure parameters. More current or less scans make a faster but less precise measure.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// EXTCHRG: the electrode charge current is `2µA * (n + 1)`, from 0 to 15
+    pub current: u8,
+    /// REFCHRG: the reference oscillator charge current is `2µA * (n + 1)`, from 0 to 15
+    pub reference_current: u8,
+    /// PS: the electrode oscillator is divided by `2^n`, from 0 to 7
+    pub prescaler: u8,
+    /// NSCN: the number of scans per electrode, summed in the count, from 1 to 32
+    pub scans: u8,
+}
+
+impl Default for Config {
+    /// The parameters used by `touch_read`, the same as the arduino `touchRead`.
+    fn default() -> Config {
+        Config {
+            current: 2,
+            reference_current: 3,
+            prescaler: 2,
+            scans: 10,
+        }
+    }
+}
+
+static mut HANDLER: Option<fn()> = None;
+
+#[repr(C, packed)]
+pub struct Tsi {
+    gencs: Volatile<u32>,
+    scanc: Volatile<u32>,
+    pen: Volatile<u32>,
+    wucntr: Volatile<u32>,
+    _reserved0: [u32; 60],
+    cntr: [Volatile<u16>; 16],
+    threshold: Volatile<u32>,
+}
+
+impl Tsi {
+    /// The TSI clock gate (`sim::Clock::Tsi`) must be enabled before using it.
+    pub unsafe fn new() -> &'static mut Tsi {
+        &mut *(0x4004_5000 as *mut Tsi)
+    }
+
+    /// Set the measure parameters. This stops the TSI.
+    pub fn configure(&mut self, config: &Config) -> Result<(), Error> {
+        if config.current > 15 {
+            return Err(Error::InvalidCurrent(config.current));
+        }
+        if config.reference_current > 15 {
+            return Err(Error::InvalidCurrent(config.reference_current));
+        }
+        if config.prescaler > 7 {
+            return Err(Error::InvalidPrescaler(config.prescaler));
+        }
+        if config.scans == 0 || config.scans > 32 {
+            return Err(Error::InvalidScanCount(config.scans));
+        }
+
+        self.stop();
+        self.scanc.update(|scanc| {
+            scanc.set_bits(24..28, config.reference_current as u32);
+            scanc.set_bits(16..20, config.current as u32);
+        });
+        self.update_gencs(|gencs| {
+            gencs.set_bits(19..24, config.scans as u32 - 1);
+            gencs.set_bits(16..19, config.prescaler as u32);
+        });
+        Ok(())
+    }
+
+    /// Mux a pin to the TSI and return its channel.
+    pub fn set_pin(&mut self, pin: impl Into<Pin>) -> Result<Channel, Error> {
+        let mut pin = pin.into();
+        let channel = Channel::of(&pin).ok_or(Error::InvalidPin)?;
+        unsafe { pin.set_pin_mode(0) };
+        Ok(channel)
+    }
+
+    /// TSIEN: the TSI is enabled, either scanning continuously or waiting for `scan`.
+    pub fn is_enabled(&self) -> bool {
+        self.gencs.read().get_bit(7)
+    }
+
+    /// Disable the TSI, which stops the continuous scan.
+    pub fn stop(&mut self) {
+        self.update_gencs(|gencs| {
+            gencs.set_bit(7, false);
+            gencs.set_bit(1, false);
+        });
+    }
+
+    /// STM: the channels are scanned continuously, see `start_continuous`.
+    pub fn is_continuous(&self) -> bool {
+        self.gencs.read().get_bit(1)
+    }
+
+    /// Measure a channel once, blocking until the end of the scan. The pin must have been muxed
+    /// to the TSI, see `set_pin`.
+    pub fn scan(&mut self, channel: Channel) -> Result<u16, Error> {
+        if self.is_continuous() {
+            return Err(Error::Busy);
+        }
+
+        self.stop();
+        self.pen.update(|pen| {
+            pen.set_bits(0..16, 1 << channel.0);
+        });
+        // clear the end of scan flag, enable the TSI and trigger the scan by software
+        self.update_gencs(|gencs| {
+            gencs.set_bit(15, true);
+            gencs.set_bit(7, true);
+            gencs.set_bit(8, true);
+        });
+
+        while !self.gencs.read().get_bit(15) {}
+        Ok(self.count(channel))
+    }
+
+    /// Scan the channels over and over, the last counts are read with `count`. The pins must
+    /// have been muxed to the TSI, see `set_pin`.
+    pub fn start_continuous(&mut self, channels: &[Channel]) {
+        self.stop();
+        let mask = channels.iter().fold(0, |mask, channel| mask | 1 << channel.0);
+        self.pen.update(|pen| {
+            pen.set_bits(0..16, mask);
+        });
+        self.scanc.update(|scanc| {
+            // SMOD: no wait between two scans
+            scanc.set_bits(8..16, 0);
+        });
+        self.update_gencs(|gencs| {
+            gencs.set_bit(1, true);
+            gencs.set_bit(7, true);
+        });
+    }
+
+    /// The count of the last scan of a channel.
+    pub fn count(&self, channel: Channel) -> u16 {
+        self.cntr[channel.0 as usize].read()
+    }
+
+    /// Raise the out of range flag when the count of `channel` is below `low` or above `high`.
+    /// Only one channel is checked.
+    pub fn set_threshold(&mut self, channel: Channel, low: u16, high: u16) {
+        self.pen.update(|pen| {
+            pen.set_bits(16..20, channel.0 as u32);
+        });
+        let mut threshold = 0;
+        threshold.set_bits(0..16, high as u32);
+        threshold.set_bits(16..32, low as u32);
+        self.threshold.write(threshold);
+    }
+
+    /// OUTRGF: the count of the channel given to `set_threshold` went out of range.
+    pub fn is_out_of_range(&self) -> bool {
+        self.gencs.read().get_bit(14)
+    }
+
+    pub fn clear_out_of_range(&mut self) {
+        self.update_gencs(|gencs| {
+            gencs.set_bit(14, true);
+        });
+    }
+
+    /// Interrupt when the count goes out of range, the handler registered with `set_handler`
+    /// is called.
+    pub fn enable_interrupt(&mut self) {
+        self.update_gencs(|gencs| {
+            // ESOR: the out of range event, not the end of scan, triggers the interrupt
+            gencs.set_bit(4, false);
+            gencs.set_bit(6, true);
+        });
+        crate::interrupts::enable(TSI_VECTOR);
+    }
+
+    pub fn disable_interrupt(&mut self) {
+        self.update_gencs(|gencs| {
+            gencs.set_bit(6, false);
+        });
+        crate::interrupts::disable(TSI_VECTOR);
+    }
+
+    /// The flags of GENCS are cleared by writing 1, they must not be written back.
+    fn update_gencs<F: FnOnce(&mut u32)>(&mut self, f: F) {
+        let mut gencs = self.gencs.read();
+        gencs.set_bits(12..16, 0);
+        f(&mut gencs);
+        self.gencs.write(gencs);
+    }
+}
+
+/// Measure the capacitance of a pin with the default `Config`, like the arduino `touchRead`.
+/// The pin is left muxed to the TSI.
+pub fn touch_read(pin: &mut Pin) -> Result<u16, Error> {
+    let channel = Channel::of(pin).ok_or(Error::InvalidPin)?;
+    unsafe {
+        crate::sim::Sim::new().enable_clock(crate::sim::Clock::Tsi);
+        let tsi = Tsi::new();
+        if tsi.is_continuous() {
+            return Err(Error::Busy);
+        }
+        pin.set_pin_mode(0);
+        tsi.configure(&Config::default())?;
+        tsi.scan(channel)
+    }
+}
+
+/// Register the function called when the count goes out of range. The flag is cleared for you.
+pub fn set_handler(handler: fn()) {
+    crate::interrupts::free(|| unsafe {
+        HANDLER = Some(handler);
+    });
+}
+
+/// Called from the TSI interrupt vector.
+pub(crate) unsafe fn on_interrupt() {
+    Tsi::new().clear_out_of_range();
+    if let Some(handler) = HANDLER {
+        handler();
+    }
+}
@@ -1,3 +1,6 @@
+use crate::port::mux::{Uart0, Uart1, Uart2, UartRxPin, UartTxPin};
+#[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+use crate::port::mux::{Uart3, Uart4};
 use bit_field::BitField;
 use volatile::Volatile;
 
@@ -16,11 +19,18 @@ const UART_C2_RX_ENABLE_MASK: u8 = 0x04;
 
 const UART_S1_TDRE_MASK: u8 = 0x80;
 
+/// Set when a UART was taken by `uart0`, `uart1`...
+static mut TAKEN: [bool; 5] = [false; 5];
+
 #[allow(non_camel_case_types)]
 pub enum Available_UART {
     UART0,
     UART1,
     UART2,
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+    UART3,
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+    UART4,
 }
 
 pub use Available_UART::*;
@@ -94,6 +104,7 @@ pub struct UART {
 }
 
 impl UART {
+    /// Reconfigure the pins of the UART without owning them, prefer `uart0`, `uart1` or `uart2`.
     pub unsafe fn new(num: Available_UART) -> &'static mut UART {
         &mut *match num {
             UART0 => {
@@ -117,14 +128,102 @@ impl UART {
                 tx.set_pin_mode(3);
                 UART2_BASE_PTR as *mut UART
             }
+            #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+            UART3 => {
+                let (mut rx, mut tx) = (crate::port::Pin::new(31), crate::port::Pin::new(32));
+                rx.set_pin_mode(3);
+                rx.set_pin_ps(true);
+                tx.set_pin_mode(3);
+                UART3_BASE_PTR as *mut UART
+            }
+            #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+            UART4 => {
+                let (mut rx, mut tx) = (crate::port::Pin::new(34), crate::port::Pin::new(33));
+                rx.set_pin_mode(3);
+                rx.set_pin_ps(true);
+                tx.set_pin_mode(3);
+                UART4_BASE_PTR as *mut UART
+            }
         }
     }
+    /// Take the UART0, on the pins 0 (RX) and 1 (TX) or 21 (RX) and 5 (TX).
+    /// Give the pins back, left untouched, if it was already taken.
+    pub fn uart0<RX: UartRxPin<Uart0>, TX: UartTxPin<Uart0>>(
+        rx: RX,
+        tx: TX,
+    ) -> Result<&'static mut UART, (RX, TX)> {
+        UART::take(0, UART0_BASE_PTR, rx, tx)
+    }
+
+    /// Take the UART1, on the pins 9 (RX) and 10 (TX) or 26 (RX) and 31 (TX).
+    /// Give the pins back, left untouched, if it was already taken.
+    pub fn uart1<RX: UartRxPin<Uart1>, TX: UartTxPin<Uart1>>(
+        rx: RX,
+        tx: TX,
+    ) -> Result<&'static mut UART, (RX, TX)> {
+        UART::take(1, UART1_BASE_PTR, rx, tx)
+    }
+
+    /// Take the UART2, on the pins 7 (RX) and 8 (TX).
+    /// Give the pins back, left untouched, if it was already taken.
+    pub fn uart2<RX: UartRxPin<Uart2>, TX: UartTxPin<Uart2>>(
+        rx: RX,
+        tx: TX,
+    ) -> Result<&'static mut UART, (RX, TX)> {
+        UART::take(2, UART2_BASE_PTR, rx, tx)
+    }
+
+    /// Take the UART3, on the pins 31 (RX) and 32 (TX).
+    /// Give the pins back, left untouched, if it was already taken.
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+    pub fn uart3<RX: UartRxPin<Uart3>, TX: UartTxPin<Uart3>>(
+        rx: RX,
+        tx: TX,
+    ) -> Result<&'static mut UART, (RX, TX)> {
+        UART::take(3, UART3_BASE_PTR, rx, tx)
+    }
+
+    /// Take the UART4, on the pins 34 (RX) and 33 (TX).
+    /// Give the pins back, left untouched, if it was already taken.
+    #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+    pub fn uart4<RX: UartRxPin<Uart4>, TX: UartTxPin<Uart4>>(
+        rx: RX,
+        tx: TX,
+    ) -> Result<&'static mut UART, (RX, TX)> {
+        UART::take(4, UART4_BASE_PTR, rx, tx)
+    }
+
+    /// A UART can be muxed on several pairs of pins, so owning the pins isn't enough: the
+    /// `TAKEN` flag ensures there is only one UART value per base address. It is checked before
+    /// muxing the pins, so a second pair is never connected to a taken UART.
+    fn take<U, RX: UartRxPin<U>, TX: UartTxPin<U>>(
+        n: usize,
+        base: u32,
+        rx: RX,
+        tx: TX,
+    ) -> Result<&'static mut UART, (RX, TX)> {
+        crate::interrupts::free(|| unsafe {
+            if TAKEN[n] {
+                return Err((rx, tx));
+            }
+            TAKEN[n] = true;
+            let mut rx: crate::port::Pin = rx.mux().into();
+            tx.mux();
+            rx.set_pin_ps(true);
+            Ok(&mut *(base as *mut UART))
+        })
+    }
+
     pub fn name(&self) -> Available_UART {
         let addr = (self as *const UART) as u32;
         match addr {
             UART0_BASE_PTR => UART0,
             UART1_BASE_PTR => UART1,
             UART2_BASE_PTR => UART2,
+            #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+            UART3_BASE_PTR => UART3,
+            #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+            UART4_BASE_PTR => UART4,
             _ => unreachable!(),
         }
     }
@@ -142,6 +241,14 @@ impl UART {
             UART2 => sim.scgc4.update(|scgc4| {
                 scgc4.set_bit(12, true);
             }),
+            #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+            UART3 => sim.scgc4.update(|scgc4| {
+                scgc4.set_bit(13, true);
+            }),
+            #[cfg(any(feature = "teensy_35", feature = "teensy_36"))]
+            UART4 => sim.scgc1.update(|scgc1| {
+                scgc1.set_bit(10, true);
+            }),
         }
 
         // 8 bit no parity
@@ -153,9 +260,13 @@ impl UART {
         // C4_BRFA = The fine adjust value
         //
         // tx baud = module clock / (16 * (divisor + BRFA / 32))
-        let f_cpu = crate::mcg::F_CPU;
-        let divisor = f_cpu / (baud * 16);
-        let brfa = ((2 * f_cpu) / baud - divisor * 32) as u8;
+        // The UART0 and UART1 are clocked by the core, the others by the bus
+        let clock = match self.name() {
+            UART0 | UART1 => crate::mcg::F_CPU,
+            _ => crate::mcg::F_BUS,
+        };
+        let divisor = clock / (baud * 16);
+        let brfa = ((2 * clock) / baud - divisor * 32) as u8;
 
         self.BDH.write((divisor >> 8) as u8 & UART_BDH_MASK);
         self.BDL.write(divisor as u8 & UART_BDL_MASK);
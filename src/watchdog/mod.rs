@@ -0,0 +1,253 @@
+//! # Watchdog
+//! The watchdog is a piece of hardware which will reset the microcontroller unless the running
+//! application “checks in” in a certain interval. It’s designed to restart crashed or hung
+//! programs.
+//!
+//! By default the `init` function of this crate disable the watchdog, but leaves its
+//! configuration unlocked (ALLOWUPDATE) so it can be enabled again from your `main`. Provide your
+//! own `init` (see the `manual_init` feature) only to watch the clock setup too. Then configure
+//! it:
+//! ```rust
+//! let wdog = unsafe { watchdog::Watchdog::new() };
+//! wdog.configure(watchdog::WatchdogConfig {
+//!     timeout: core::time::Duration::from_millis(500),
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//! ```
+//! Then call `feed` regularly from your main loop, or let a `supervisor::Supervisor` do it.
+
+pub mod supervisor;
+
+use bit_field::BitField;
+use core::arch::arm::__nop;
+use core::time::Duration;
+use volatile::Volatile;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The configuration was locked by a previous `configure` with `allow_update` unset.
+    /// Only a reset can unlock it.
+    Locked,
+    /// The timeout is too short (less than 4 watchdog clock cycles) or too long.
+    InvalidTimeout,
+    /// The window must be shorter than the timeout.
+    InvalidWindow,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ClockSource {
+    /// The 1kHz low power oscillator
+    Lpo = 0,
+    /// The alternate clock, which is the bus clock on the teensy
+    Alternate = 1,
+}
+
+impl ClockSource {
+    fn frequency(self) -> u32 {
+        match self {
+            ClockSource::Lpo => 1_000,
+            ClockSource::Alternate => crate::mcg::F_BUS,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct WatchdogConfig {
+    /// Time after which the watchdog reset the chip if it was not fed
+    pub timeout: Duration,
+    /// If set, feeding the watchdog before this duration has elapsed also reset the chip
+    pub window: Option<Duration>,
+    pub clock_source: ClockSource,
+    /// Allow the watchdog to be reconfigured later, if unset the configuration is locked until
+    /// the next reset
+    pub allow_update: bool,
+    /// Trigger the watchdog interrupt (see `set_interrupt_handler`) before the reset. The chip
+    /// is still reset 256 bus clock cycles later.
+    pub irq_before_reset: bool,
+    /// Keep the watchdog running while the CPU is halted by a debugger
+    pub run_in_debug: bool,
+    /// Keep the watchdog running in STOP mode
+    pub run_in_stop: bool,
+    /// Keep the watchdog running in WAIT mode
+    pub run_in_wait: bool,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        WatchdogConfig {
+            timeout: Duration::from_secs(1),
+            window: None,
+            clock_source: ClockSource::Lpo,
+            allow_update: true,
+            irq_before_reset: false,
+            run_in_debug: false,
+            run_in_stop: true,
+            run_in_wait: true,
+        }
+    }
+}
+
+/// Convert a duration into a number of cycles of a clock running at `frequency` divided by
+/// `prescaler`. `None` if the computation overflows.
+fn cycles(duration: Duration, frequency: u32, prescaler: u32) -> Option<u64> {
+    let micros = duration.as_micros();
+    if micros > u64::MAX as u128 {
+        return None;
+    }
+    Some((micros as u64).checked_mul(frequency as u64)? / 1_000_000 / prescaler as u64)
+}
+
+static mut INTERRUPT_HANDLER: Option<fn()> = None;
+
+#[repr(C, packed)]
+pub struct Watchdog {
+    stctrlh: Volatile<u16>,
+    stctrll: Volatile<u16>,
+    tovalh: Volatile<u16>,
+    tovall: Volatile<u16>,
+    winh: Volatile<u16>,
+    winl: Volatile<u16>,
+    refresh: Volatile<u16>,
+    unlock: Volatile<u16>,
+    tmrouth: Volatile<u16>,
+    tmroutl: Volatile<u16>,
+    rstcnt: Volatile<u16>,
+    presc: Volatile<u16>,
+}
+
+impl Watchdog {
+    pub unsafe fn new() -> &'static mut Watchdog {
+        &mut *(0x4005_2000 as *mut Watchdog)
+    }
+
+    /// The unlock sequence must be written in less than 20 bus cycles and the configuration
+    /// in less than 256 bus cycles, this must be called with the interrupts disabled.
+    fn unlock(&mut self) {
+        unsafe {
+            self.unlock.write(0xC520);
+            self.unlock.write(0xD928);
+            __nop();
+            __nop();
+        }
+    }
+
+    pub fn disable(&mut self) {
+        crate::interrupts::free(|| {
+            self.unlock();
+            self.stctrlh.update(|ctrl| {
+                ctrl.set_bit(0, false);
+            });
+        });
+    }
+
+    /// Configure and enable the watchdog.
+    pub fn configure(&mut self, config: WatchdogConfig) -> Result<(), Error> {
+        if !self.stctrlh.read().get_bit(4) {
+            return Err(Error::Locked);
+        }
+
+        // find the smallest prescaler letting our timeout fit in the 32 bits of TOVAL
+        let frequency = config.clock_source.frequency();
+        let cycles_at_1 = cycles(config.timeout, frequency, 1).ok_or(Error::InvalidTimeout)?;
+        let prescaler = (1..=8)
+            .find(|prescaler| cycles_at_1 / *prescaler as u64 <= u32::MAX as u64)
+            .ok_or(Error::InvalidTimeout)?;
+        let timeout = (cycles_at_1 / prescaler as u64) as u32;
+        if timeout < 4 {
+            return Err(Error::InvalidTimeout);
+        }
+
+        let window = match config.window {
+            Some(window) => {
+                let window = cycles(window, frequency, prescaler).ok_or(Error::InvalidWindow)?;
+                if window >= timeout as u64 {
+                    return Err(Error::InvalidWindow);
+                }
+                window as u32
+            }
+            None => 0,
+        };
+
+        crate::interrupts::free(|| {
+            self.unlock();
+            self.tovalh.write((timeout >> 16) as u16);
+            self.tovall.write(timeout as u16);
+            self.winh.write((window >> 16) as u16);
+            self.winl.write(window as u16);
+            self.presc.write(((prescaler - 1) as u16) << 8);
+            self.stctrlh.update(|ctrl| {
+                ctrl.set_bit(0, true);
+                ctrl.set_bit(1, config.clock_source == ClockSource::Alternate);
+                ctrl.set_bit(2, config.irq_before_reset);
+                ctrl.set_bit(3, config.window.is_some());
+                ctrl.set_bit(4, config.allow_update);
+                ctrl.set_bit(5, config.run_in_debug);
+                ctrl.set_bit(6, config.run_in_stop);
+                ctrl.set_bit(7, config.run_in_wait);
+            });
+        });
+
+        if config.irq_before_reset {
+            crate::interrupts::enable(crate::board::vectors::WDOG_EWM);
+        }
+        Ok(())
+    }
+
+    /// Refresh the watchdog. In window mode this will reset the chip if it is called before the
+    /// window duration.
+    pub fn feed(&mut self) {
+        crate::interrupts::free(|| {
+            self.refresh.write(0xA602);
+            self.refresh.write(0xB480);
+        });
+    }
+
+    /// Number of watchdog cycles elapsed since the last refresh.
+    pub fn timer_output(&self) -> u32 {
+        (self.tmrouth.read() as u32) << 16 | self.tmroutl.read() as u32
+    }
+
+    /// Number of times the watchdog reset the chip since the last power on reset.
+    pub fn reset_count(&self) -> u16 {
+        self.rstcnt.read()
+    }
+
+    pub fn clear_reset_count(&mut self) {
+        // write one to clear each bit
+        self.rstcnt.write(0xFFFF);
+    }
+
+    /// INTFLG: the watchdog timed out and the chip is about to be reset.
+    pub fn interrupt_pending(&self) -> bool {
+        self.stctrll.read().get_bit(15)
+    }
+
+    pub fn clear_interrupt(&mut self) {
+        self.stctrll.update(|ctrl| {
+            ctrl.set_bit(15, true);
+        });
+    }
+}
+
+/// Register a function to call when the watchdog interrupt fires, see
+/// `WatchdogConfig::irq_before_reset`. The function has 256 bus cycles to run before the reset.
+pub fn set_interrupt_handler(handler: fn()) {
+    crate::interrupts::free(|| unsafe {
+        INTERRUPT_HANDLER = Some(handler);
+    });
+}
+
+/// Called from the interrupt vector shared with the EWM. Return false if the watchdog was not
+/// the source of the interrupt.
+pub(crate) unsafe fn on_interrupt() -> bool {
+    let wdog = Watchdog::new();
+    if !wdog.interrupt_pending() {
+        return false;
+    }
+    wdog.clear_interrupt();
+    if let Some(handler) = INTERRUPT_HANDLER {
+        handler();
+    }
+    true
+}
@@ -0,0 +1,185 @@
+//! # Watchdog supervisor
+//! Feeding the watchdog from a single place only proves that this place is still running. If
+//! the watchdog is fed from an interrupt, a hung main loop will never be detected.
+//!
+//! The supervisor let multiple tasks register with their own deadline. Each task must
+//! `check_in` before its deadline, and the hardware watchdog is only fed if **all** the tasks
+//! checked in on time. When a task miss its deadline the supervisor stops feeding the watchdog
+//! and records the task in a part of the RAM that survives the reset, so it can be reported with
+//! `last_failure` after the reboot.
+//!
+//! There is a single supervisor, so that the tasks can `check_in` from anywhere, including the
+//! interrupt handlers.
+//! ```rust
+//! let wdog = unsafe { watchdog::Watchdog::new() };
+//! wdog.configure(watchdog::WatchdogConfig::default()).unwrap();
+//! let mut supervisor = watchdog::supervisor::Supervisor::take(wdog).unwrap();
+//! // the deadlines are expressed in number of calls to `tick`
+//! let main_loop = supervisor.register(100).unwrap();
+//!
+//! loop {
+//!     watchdog::supervisor::check_in(main_loop);
+//!     // your code
+//!     supervisor.tick().ok();
+//!     sleep::sleep_ms(1);
+//! }
+//! ```
+
+use super::Watchdog;
+
+/// Maximum number of tasks the supervisor can follow.
+pub const MAX_TASKS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// All the `MAX_TASKS` slots are already used
+    TooManyTasks,
+    /// A task needs at least one tick to check in
+    InvalidDeadline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskId(usize);
+
+impl TaskId {
+    /// The index of the task, in registration order.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Task {
+    deadline: u32,
+    elapsed: u32,
+}
+
+static mut TASKS: [Option<Task>; MAX_TASKS] = [None; MAX_TASKS];
+static mut TAKEN: bool = false;
+
+/// System Reset Status Register 0 of the Reset Control Module, WDOG is its bit 5.
+const RCM_SRS0: u32 = 0x4007_F000;
+
+/// Written just before the reset, see the `.noinit` section of `layout.ld`.
+/// After a power on reset the content is random so we check the magic value.
+const FAILURE_MAGIC: u32 = 0x5AFE_D06E;
+#[link_section = ".noinit"]
+static mut LAST_FAILURE: [u32; 2] = [0; 2];
+
+pub struct Supervisor {
+    wdog: &'static mut Watchdog,
+}
+
+impl Supervisor {
+    /// Take control of an already configured watchdog. From now on only the supervisor should
+    /// feed it. Return `None` if the supervisor was already taken.
+    pub fn take(wdog: &'static mut Watchdog) -> Option<Self> {
+        crate::interrupts::free(move || unsafe {
+            if TAKEN {
+                None
+            } else {
+                TAKEN = true;
+                Some(Supervisor { wdog })
+            }
+        })
+    }
+
+    /// Register a new task which must check in at least every `deadline` ticks.
+    pub fn register(&mut self, deadline: u32) -> Result<TaskId, Error> {
+        if deadline == 0 {
+            return Err(Error::InvalidDeadline);
+        }
+
+        crate::interrupts::free(|| unsafe {
+            let slot = TASKS
+                .iter()
+                .position(|task| task.is_none())
+                .ok_or(Error::TooManyTasks)?;
+            TASKS[slot] = Some(Task {
+                deadline,
+                elapsed: 0,
+            });
+            Ok(TaskId(slot))
+        })
+    }
+
+    /// Stop following a task.
+    pub fn unregister(&mut self, task: TaskId) {
+        crate::interrupts::free(|| unsafe {
+            TASKS[task.0] = None;
+        });
+    }
+
+    /// Advance the time of all the tasks by one tick and feed the watchdog if they all checked
+    /// in before their deadline. This should be called at a fixed rate, from the main loop or
+    /// from a timer interrupt.
+    ///
+    /// If a task missed its deadline it is recorded and returned, and the watchdog is not fed
+    /// anymore: the chip will be reset once its timeout expires.
+    pub fn tick(&mut self) -> Result<(), TaskId> {
+        let late = crate::interrupts::free(|| unsafe {
+            let mut late = None;
+            for (id, task) in TASKS.iter_mut().enumerate() {
+                if let Some(task) = task {
+                    task.elapsed = task.elapsed.saturating_add(1);
+                    if task.elapsed > task.deadline && late.is_none() {
+                        late = Some(TaskId(id));
+                    }
+                }
+            }
+            late
+        });
+
+        match late {
+            None => {
+                self.wdog.feed();
+                Ok(())
+            }
+            Some(task) => {
+                unsafe {
+                    core::ptr::write_volatile(&mut LAST_FAILURE[0], FAILURE_MAGIC);
+                    core::ptr::write_volatile(&mut LAST_FAILURE[1], task.0 as u32);
+                }
+                Err(task)
+            }
+        }
+    }
+}
+
+/// Signal that a task is still alive. Can be called from any context, including interrupts.
+pub fn check_in(task: TaskId) {
+    crate::interrupts::free(|| unsafe {
+        if let Some(task) = &mut TASKS[task.0] {
+            task.elapsed = 0;
+        }
+    });
+}
+
+/// The task which missed its deadline and caused the last watchdog reset, if any. `None` if the
+/// last reset was not caused by the watchdog.
+pub fn last_failure() -> Option<TaskId> {
+    let watchdog_reset = unsafe { core::ptr::read_volatile(RCM_SRS0 as *const u8) } & 1 << 5 != 0;
+    if !watchdog_reset {
+        return None;
+    }
+
+    let (magic, task) = unsafe {
+        (
+            core::ptr::read_volatile(&LAST_FAILURE[0]),
+            core::ptr::read_volatile(&LAST_FAILURE[1]),
+        )
+    };
+
+    if magic == FAILURE_MAGIC && (task as usize) < MAX_TASKS {
+        Some(TaskId(task as usize))
+    } else {
+        None
+    }
+}
+
+/// Forget the last failure, call it once it has been reported.
+pub fn clear_last_failure() {
+    unsafe {
+        core::ptr::write_volatile(&mut LAST_FAILURE[0], 0);
+    }
+}